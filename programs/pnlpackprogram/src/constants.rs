@@ -1,3 +1,40 @@
 use anchor_lang::prelude::*;
 
-pub static ADMIN_KEY: Pubkey = pubkey!("7E85TTXg5FjT5G6q14nZUSE3KAgjM2kjBs8ddAW6eBeR");
\ No newline at end of file
+// only used to bootstrap the global pack pool; afterwards the authority stored
+// on GlobalPackPool gates every privileged instruction
+pub static ADMIN_KEY: Pubkey = pubkey!("7E85TTXg5FjT5G6q14nZUSE3KAgjM2kjBs8ddAW6eBeR");
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// keep in sync with the max_len on GlobalPackPool::fee_recipients
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+
+// keep in sync with the max_len on GlobalPackPool::kol_registry
+pub const MAX_KOLS: usize = 64;
+pub const MAX_KOL_TICKER_LEN: usize = 16;
+pub const MAX_KOL_NAME_LEN: usize = 32;
+
+// keep in sync with the max_len on Pack::slots
+pub const MIN_KOLS_PER_PACK: u8 = 1;
+pub const MAX_KOLS_PER_PACK: u8 = 8;
+
+// slots between commit_pack and the slot whose hash seeds the reveal
+pub const REVEAL_DELAY_SLOTS: u64 = 4;
+
+// keep in sync with the max_len attributes on GlobalPackPool::tiers and its nested tables
+pub const MAX_TIERS: usize = 3;
+pub const MAX_SLOT_OUTCOMES: usize = 4;
+
+// keep in sync with the max_len attributes on PackCollection
+pub const MAX_PACK_NFT_NAME_LEN: usize = 20;
+pub const MAX_NFT_SYMBOL_LEN: usize = 10;
+pub const MAX_NFT_URI_LEN: usize = 200;
+
+// keeps the claim bitmap within the 10KiB an account can be created with
+pub const MAX_DISTRIBUTOR_LEAVES: u32 = 64_000;
+
+// keep in sync with the max_len on Distributor::reserves
+pub const MAX_DISTRIBUTOR_KOLS: usize = 8;
+
+// keep in sync with the max_len on Leaderboard::entries
+pub const LEADERBOARD_SIZE: usize = 10;
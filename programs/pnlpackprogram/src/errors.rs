@@ -10,4 +10,196 @@ pub enum ErrorCode {
 
     #[msg("Seed longer than 32 bytes")] 
     InvalidSeedLength,
+
+    #[msg("Signer is not the program authority")]
+    Unauthorized,
+
+    #[msg("No authority handover has been proposed")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the proposed authority")]
+    NotPendingAuthority,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Invalid raise configuration")]
+    InvalidRaiseConfig,
+
+    #[msg("Raise has not started yet")]
+    RaiseNotStarted,
+
+    #[msg("Raise has ended")]
+    RaiseEnded,
+
+    #[msg("Contribution would exceed the raise hard cap")]
+    HardCapExceeded,
+
+    #[msg("Contribution is below the per-wallet minimum")]
+    ContributionBelowMinimum,
+
+    #[msg("Contribution would exceed the per-wallet maximum")]
+    ContributionAboveMaximum,
+
+    #[msg("Raise has not failed, refunds are unavailable")]
+    RefundUnavailable,
+
+    #[msg("Contribution has already been refunded")]
+    AlreadyRefunded,
+
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+
+    #[msg("Fee recipients must be non-empty, at most 5 and sum to 10000 bps")]
+    InvalidFeeRecipients,
+
+    #[msg("Withdrawal would leave the pool below rent exemption")]
+    InsufficientTreasury,
+
+    #[msg("Recipient accounts do not match the configured fee recipients")]
+    FeeRecipientMismatch,
+
+    #[msg("Signer does not own this pack")]
+    NotPackOwner,
+
+    #[msg("KOL is not part of this pack")]
+    KolNotInPack,
+
+    #[msg("Pack has nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Pack sales are not open")]
+    PackSaleClosed,
+
+    #[msg("Pack has already been sold")]
+    PackAlreadySold,
+
+    #[msg("KOL registry is full")]
+    KolRegistryFull,
+
+    #[msg("total_kols exceeds the registry capacity of 64")]
+    TooManyKols,
+
+    #[msg("KOL ticker longer than 16 bytes")]
+    KolTickerTooLong,
+
+    #[msg("Pack has already been revealed")]
+    PackAlreadyRevealed,
+
+    #[msg("Pack has not been revealed yet")]
+    PackNotRevealed,
+
+    #[msg("Reveal slot has not passed yet")]
+    RandomnessNotReady,
+
+    #[msg("SlotHashes sysvar data is malformed")]
+    InvalidSlotHashes,
+
+    #[msg("KOL display name longer than 32 bytes")]
+    KolNameTooLong,
+
+    #[msg("Mint does not match the KOL registry")]
+    KolMintMismatch,
+
+    #[msg("KOL record does not match the drawn KOL")]
+    KolRecordMismatch,
+
+    #[msg("Pack size must be between 1 and 8 KOLs")]
+    InvalidPackSize,
+
+    #[msg("Remaining accounts do not match the pack slots")]
+    PackAccountsMismatch,
+
+    #[msg("Invalid pack tier configuration")]
+    InvalidTierConfig,
+
+    #[msg("Funding would exceed the slot's rolled amount")]
+    SlotTargetExceeded,
+
+    #[msg("Pack is not in a state that allows this action")]
+    InvalidPackStatus,
+
+    #[msg("Claim exceeds the remaining allocation")]
+    ClaimExceedsAllocation,
+
+    #[msg("Pack collection has not been initialized")]
+    PackCollectionNotInitialized,
+
+    #[msg("Pack collection has already been initialized")]
+    PackCollectionAlreadyInitialized,
+
+    #[msg("NFT metadata field too long")]
+    NftMetadataTooLong,
+
+    #[msg("Pack NFT accounts are missing or do not match the pack")]
+    PackNftMismatch,
+
+    #[msg("KOL supply is already locked")]
+    KolSupplyLocked,
+
+    #[msg("KOL mint supply does not match the recorded max supply")]
+    KolSupplyMismatch,
+
+    #[msg("Allocation exceeds the uncommitted vault balance")]
+    VaultOverAllocated,
+
+    #[msg("Distributor must have between 1 and MAX_DISTRIBUTOR_LEAVES leaves")]
+    InvalidDistributorConfig,
+
+    #[msg("Leaf index is outside the distributor")]
+    LeafIndexOutOfRange,
+
+    #[msg("Merkle proof does not match the distributor root")]
+    InvalidMerkleProof,
+
+    #[msg("Allocation already claimed")]
+    AlreadyClaimed,
+
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingConfig,
+
+    #[msg("Nothing has unlocked yet")]
+    NothingVested,
+
+    #[msg("This instruction is paused")]
+    ProgramPaused,
+
+    #[msg("Role cannot be granted, the authority is transferred with propose_authority")]
+    InvalidRole,
+
+    #[msg("PnL epoch must increase with every reading")]
+    PnlEpochNotIncreasing,
+
+    #[msg("PnL reading timestamp is out of order or in the future")]
+    InvalidPnlTimestamp,
+
+    #[msg("PnL moved more than the allowed delta")]
+    PnlDeltaExceeded,
+
+    #[msg("Season end epoch must not be before its start epoch")]
+    InvalidSeason,
+
+    #[msg("KOL PnL reading is not the one at the season start or end epoch")]
+    PnlOutsideSeason,
+
+    #[msg("Distributor claim exceeds the amount reserved for this KOL")]
+    DistributorReserveExceeded,
+
+    #[msg("Packs are waiting to be revealed, the KOL registry and tiers are frozen until then")]
+    RevealsPending,
+
+    #[msg("Listed packs can only be revealed once bought")]
+    PackNotSold,
+
+    #[msg("Reveal slot hash has expired, the pack must be re-committed by an operator")]
+    RevealSlotExpired,
+
+    #[msg("Reveal slot hash is still available, the pack can be revealed")]
+    RevealSlotNotExpired,
+
+    #[msg("Every slot of a tiered pack must be funded to its target before it can be claimed")]
+    PackNotFullyFunded,
+
+    #[msg("Pack was funded after the season's start reading")]
+    PackFundedAfterSeasonStart,
 }
@@ -0,0 +1,46 @@
+// events emitted by the program
+
+use anchor_lang::prelude::*;
+use crate::state::{FeeRecipientKind, PackTier};
+
+#[event]
+pub struct TreasuryPayout {
+    pub recipient: Pubkey,
+    pub kind: FeeRecipientKind,
+    pub bps: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PackRevealed {
+    pub pack: Pubkey,
+    pub id: u64,
+    pub reveal_slot: u64,
+    pub slot_hash: [u8; 32],
+    pub randomness_seed: [u8; 32],
+    pub tier: Option<PackTier>,
+    pub kols: Vec<String>,
+}
+
+#[event]
+pub struct PackRecommitted {
+    pub pack: Pubkey,
+    pub id: u64,
+    pub recommits: u32,
+    pub reveal_slot: u64,
+}
+
+#[event]
+pub struct PackScored {
+    pub pack: Pubkey,
+    pub season: u32,
+    pub score: i64,
+}
+
+#[event]
+pub struct KolPnlRecorded {
+    pub kol_record: Pubkey,
+    pub epoch: u64,
+    pub pnl: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::GlobalPackPool;
+
+// step 2 of the authority handover: the nominated key signs to take over
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  pub new_authority: Signer<'info>,
+}
+
+impl<'info> AcceptAuthority<'info> {
+  pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let pool = &mut ctx.accounts.global_pack_pool;
+    let pending = pool.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
+    require_keys_eq!(pending, ctx.accounts.new_authority.key(), ErrorCode::NotPendingAuthority);
+
+    msg!("Authority handed over from {} to {}", pool.authority, pending);
+    pool.authority = pending;
+    pool.pending_authority = None;
+    Ok(())
+  }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken, Create};
+use anchor_spl::token_interface::{transfer_checked, TokenInterface, TransferChecked};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, Pack, PackStatus, Role, RoleAssignment};
+
+// accounts passed per funding entry, in entry order
+const ACCOUNTS_PER_FUNDING: usize = 5;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PackFunding {
+    pub kol: String,
+    pub amount: u64,
+}
+
+// remaining accounts: for every entry [pack (mut), KolRecord (mut), mint, vault (mut), pack ATA (mut)],
+// the same pack, record or vault may appear in several entries
+#[derive(Accounts)]
+pub struct BatchTransferToPacks<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.funding @ ErrorCode::ProgramPaused
+    )]
+    pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+    #[account(
+        mut,
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Operator) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> BatchTransferToPacks<'info> {
+    pub fn handler(ctx: Context<'_, '_, 'info, 'info, BatchTransferToPacks<'info>>, entries: Vec<PackFunding>) -> Result<()> {
+        require!(
+            !entries.is_empty() && ctx.remaining_accounts.len() == entries.len() * ACCOUNTS_PER_FUNDING,
+            ErrorCode::PackAccountsMismatch
+        );
+
+        let pool_key = ctx.accounts.global_pack_pool.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global_pack_pool", &[ctx.accounts.global_pack_pool.bump]]];
+        let pool_info = ctx.accounts.global_pack_pool.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let token_program_id = ctx.accounts.token_program.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        for (entry, accounts) in entries.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_FUNDING)) {
+            let [pack_info, record_info, mint_info, vault_info, pack_ta] = accounts else {
+                return err!(ErrorCode::PackAccountsMismatch);
+            };
+
+            // accounts are re-read per entry, so repeats see the previous entry's writes
+            let mut pack = Account::<Pack>::try_from(pack_info)?;
+            let pack_pda = Pubkey::create_program_address(&[b"pack", pack.id.to_le_bytes().as_ref(), &[pack.bump]], &crate::ID)
+                .map_err(|_| ErrorCode::InvalidPackPda)?;
+            require_keys_eq!(pack_pda, pack.key(), ErrorCode::InvalidPackPda);
+            require!(matches!(pack.status, PackStatus::Revealed | PackStatus::Funded | PackStatus::Claimed), ErrorCode::PackNotRevealed);
+
+            let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+            let record_pda = Pubkey::create_program_address(
+                &[b"kol", pool_key.as_ref(), entry.kol.as_bytes(), &[kol_record.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::KolRecordMismatch)?;
+            require_keys_eq!(record_pda, kol_record.key(), ErrorCode::KolRecordMismatch);
+            require_keys_eq!(mint_info.key(), kol_record.mint, ErrorCode::KolMintMismatch);
+            require_keys_eq!(vault_info.key(), kol_record.vault, ErrorCode::KolMintMismatch);
+            require_keys_eq!(
+                pack_ta.key(),
+                get_associated_token_address_with_program_id(&pack.key(), &kol_record.mint, &token_program_id),
+                ErrorCode::PackAccountsMismatch
+            );
+
+            pack.record_funding(&entry.kol, entry.amount, now)?;
+            // tiered slots were reserved at reveal, untiered funding is committed here
+            if pack.tier.is_none() {
+                kol_record.allocate(entry.amount)?;
+            }
+
+            associated_token::create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: ctx.accounts.admin.to_account_info(),
+                    associated_token: pack_ta.clone(),
+                    authority: pack_info.clone(),
+                    mint: mint_info.clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: token_program.clone(),
+                },
+            ))?;
+
+            let accounts = TransferChecked {
+                from: vault_info.clone(),
+                mint: mint_info.clone(),
+                to: pack_ta.clone(),
+                authority: pool_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), accounts, signer_seeds);
+            transfer_checked(cpi_ctx, entry.amount, kol_record.decimals)?;
+
+            pack.exit(&crate::ID)?;
+            kol_record.exit(&crate::ID)?;
+        }
+
+        msg!("Funded {} pack slots", entries.len());
+        Ok(())
+    }
+}
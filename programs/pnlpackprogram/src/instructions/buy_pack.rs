@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
+use anchor_spl::token::{initialize_mint2, mint_to, InitializeMint2, Mint, MintTo, Token};
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder, VerifySizedCollectionItemCpiBuilder,
+};
+use mpl_token_metadata::types::{Collection, DataV2};
+use crate::constants::REVEAL_DELAY_SLOTS;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, Pack, PackStatus};
+
+// the buyer receives a 1-of-1 NFT of the pack collection, claim_from_pack burns it.
+// only unopened packs are sold and the reveal slot is re-committed at purchase, so the
+// buyer can't know the draw before paying
+#[derive(Accounts)]
+pub struct BuyPack<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.raise @ ErrorCode::ProgramPaused
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"pack", pack_account.id.to_le_bytes().as_ref()],
+        bump = pack_account.bump,
+        constraint = pack_account.status == PackStatus::Created @ ErrorCode::PackAlreadyRevealed,
+        constraint = pack_account.owner == Pubkey::default() @ ErrorCode::PackAlreadySold
+    )]
+    pub pack_account: Box<Account<'info, Pack>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    // the NFT accounts are created by the handler rather than with init, anchor runs init before
+    // the pack_account constraints and a second buy would fail on the existing mint instead
+    /// CHECK: created by the handler, address is checked
+    #[account(
+        mut,
+        seeds = [b"pack_mint", pack_account.key().as_ref()],
+        bump
+    )]
+    pub pack_mint: UncheckedAccount<'info>,
+
+    /// CHECK: created by the associated token program, which checks the address
+    #[account(mut)]
+    pub buyer_pack_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), pack_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub pack_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), pack_mint.key().as_ref(), b"edition"],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub pack_master_edition: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"pack_collection"],
+        bump
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: validated by the token metadata program
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the token metadata program
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: address is checked
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> BuyPack<'info> {
+    pub fn handler(ctx: Context<BuyPack>) -> Result<()> {
+        let price = ctx.accounts.global_pack_pool.pack_price;
+        require!(price > 0, ErrorCode::PackSaleClosed);
+        let collection = ctx
+            .accounts
+            .global_pack_pool
+            .pack_collection
+            .clone()
+            .ok_or(ErrorCode::PackCollectionNotInitialized)?;
+        require_keys_eq!(collection.mint, ctx.accounts.collection_mint.key(), ErrorCode::PackCollectionNotInitialized);
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.global_pack_pool.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, price)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global_pack_pool", &[ctx.accounts.global_pack_pool.bump]]];
+        let pool_info = ctx.accounts.global_pack_pool.to_account_info();
+        let mint_info = ctx.accounts.pack_mint.to_account_info();
+        let buyer_info = ctx.accounts.buyer.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let rent = ctx.accounts.rent.to_account_info();
+        let collection_mint = ctx.accounts.collection_mint.to_account_info();
+
+        let pack_key = ctx.accounts.pack_account.key();
+        let mint_seeds: &[&[&[u8]]] = &[&[b"pack_mint", pack_key.as_ref(), &[ctx.bumps.pack_mint]]];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount { from: buyer_info.clone(), to: mint_info.clone() },
+                mint_seeds,
+            ),
+            Rent::get()?.minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &token_program.key(),
+        )?;
+        initialize_mint2(
+            CpiContext::new(token_program.clone(), InitializeMint2 { mint: mint_info.clone() }),
+            0,
+            &pool_info.key(),
+            Some(&pool_info.key()),
+        )?;
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: buyer_info.clone(),
+                associated_token: ctx.accounts.buyer_pack_token_account.to_account_info(),
+                authority: buyer_info.clone(),
+                mint: mint_info.clone(),
+                system_program: system_program.clone(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+
+        mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.buyer_pack_token_account.to_account_info(),
+                    authority: pool_info.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+
+        CreateMetadataAccountV3CpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .metadata(&ctx.accounts.pack_metadata)
+            .mint(&mint_info)
+            .mint_authority(&pool_info)
+            .payer(&buyer_info)
+            .update_authority(&pool_info, true)
+            .system_program(&system_program)
+            .rent(Some(&rent))
+            .data(DataV2 {
+                name: format!("{} #{}", collection.name, ctx.accounts.pack_account.id),
+                symbol: collection.symbol,
+                uri: collection.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: Some(Collection { verified: false, key: collection.mint }),
+                uses: None,
+            })
+            .is_mutable(true)
+            .invoke_signed(signer_seeds)?;
+
+        // max supply 0 makes the pack NFT a true 1-of-1 and hands the mint authority to the edition
+        CreateMasterEditionV3CpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .edition(&ctx.accounts.pack_master_edition)
+            .mint(&mint_info)
+            .update_authority(&pool_info)
+            .mint_authority(&pool_info)
+            .payer(&buyer_info)
+            .metadata(&ctx.accounts.pack_metadata)
+            .token_program(&token_program)
+            .system_program(&system_program)
+            .rent(Some(&rent))
+            .max_supply(0)
+            .invoke_signed(signer_seeds)?;
+
+        VerifySizedCollectionItemCpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .metadata(&ctx.accounts.pack_metadata)
+            .collection_authority(&pool_info)
+            .payer(&buyer_info)
+            .collection_mint(&collection_mint)
+            .collection(&ctx.accounts.collection_metadata)
+            .collection_master_edition_account(&ctx.accounts.collection_master_edition)
+            .invoke_signed(signer_seeds)?;
+
+        let pack = &mut ctx.accounts.pack_account;
+        pack.owner = ctx.accounts.buyer.key();
+        pack.nft_mint = Some(ctx.accounts.pack_mint.key());
+        pack.reveal_slot = Clock::get()?.slot.checked_add(REVEAL_DELAY_SLOTS).ok_or(ErrorCode::MathOverflow)?;
+        // the registry may have shrunk while the pack was listed
+        let pool = &mut ctx.accounts.global_pack_pool;
+        require!(pack.size as usize <= pool.kol_registry.len(), ErrorCode::NotEnoughKols);
+        pack.registry_len = pool.kol_registry.len() as u16;
+        pool.unrevealed_packs = pool.unrevealed_packs.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        pool.packs_sold = pool.packs_sold.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        pool.sales_revenue = pool.sales_revenue.checked_add(price).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Pack {} sold to {} for {} lamports", ctx.accounts.pack_account.key(), ctx.accounts.buyer.key(), price);
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, Pack, PackStatus, Role, RoleAssignment};
+
+// withdraws a pack that was never bought nor funded, listed or handed out by commit_pack:
+// the vault tokens reserved for a tiered pack at reveal are handed back and the rent returns
+// to pack.payer. Packs bought with buy_pack have to be funded instead, the buyer paid for them
+//
+// remaining accounts: the KolRecord (mut) of every slot, in slot order
+#[derive(Accounts)]
+pub struct CancelPack<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Operator) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"pack", pack.id.to_le_bytes().as_ref()],
+        bump = pack.bump,
+        constraint = matches!(pack.status, PackStatus::Created | PackStatus::Revealed) @ ErrorCode::InvalidPackStatus,
+        constraint = pack.nft_mint.is_none() @ ErrorCode::PackAlreadySold,
+        close = payer
+    )]
+    pub pack: Box<Account<'info, Pack>>,
+
+    #[account(
+        mut,
+        address = pack.payer @ ErrorCode::Unauthorized
+    )]
+    pub payer: SystemAccount<'info>,
+}
+
+impl<'info> CancelPack<'info> {
+    pub fn handler(ctx: Context<'_, '_, 'info, 'info, CancelPack<'info>>) -> Result<()> {
+        let pack = &ctx.accounts.pack;
+        require!(ctx.remaining_accounts.len() == pack.slots.len(), ErrorCode::PackAccountsMismatch);
+
+        // untiered packs only allocate when funded, so there is nothing to hand back
+        if pack.tier.is_some() {
+            for (slot, record_info) in pack.slots.iter().zip(ctx.remaining_accounts.iter()) {
+                let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+                require!(kol_record.ticker == slot.ticker, ErrorCode::KolRecordMismatch);
+                kol_record.release(slot.target)?;
+                kol_record.exit(&crate::ID)?;
+            }
+        }
+
+        // listed packs never entered the count, see commit_pack
+        if pack.status == PackStatus::Created && pack.owner != Pubkey::default() {
+            let pool = &mut ctx.accounts.global_pack_pool;
+            pool.unrevealed_packs = pool.unrevealed_packs.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        msg!("Pack {} cancelled, rent returned to {}", pack.id, ctx.accounts.payer.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked};
+use crate::errors::ErrorCode;
+use crate::merkle;
+use crate::state::{Distributor, GlobalPackPool, KolRecord};
+
+// pays one merkle leaf straight from the KOL vault, the leaf's bit in the distributor
+// bitmap stops it from being claimed twice
+#[derive(Accounts)]
+#[instruction(kol: String)]
+pub struct ClaimFromDistributor<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.claims @ ErrorCode::ProgramPaused
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor", global_pack_pool.key().as_ref(), distributor.id.to_le_bytes().as_ref()],
+        bump = distributor.bump
+    )]
+    pub distributor: Box<Account<'info, Distributor>>,
+
+    #[account(
+        mut,
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol.as_bytes()],
+        bump = kol_record.bump,
+        constraint = kol_record.mint == kol_mint.key() @ ErrorCode::KolMintMismatch,
+        constraint = kol_record.vault == kol_token_vault.key() @ ErrorCode::KolMintMismatch
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub kol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = kol_mint,
+        token::authority = global_pack_pool,
+        token::token_program = token_program
+    )]
+    pub kol_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = kol_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ClaimFromDistributor<'info> {
+    pub fn handler(ctx: Context<ClaimFromDistributor>, kol: String, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let distributor = &ctx.accounts.distributor;
+        require!(index < distributor.num_leaves, ErrorCode::LeafIndexOutOfRange);
+        require!(!distributor.is_claimed(index), ErrorCode::AlreadyClaimed);
+        let leaf = merkle::leaf_hash(index, &ctx.accounts.user.key(), &ctx.accounts.kol_mint.key(), amount);
+        require!(merkle::verify(&proof, &distributor.merkle_root, leaf), ErrorCode::InvalidMerkleProof);
+
+        // paid out of the reservation made by create_distributor, the vault allocation is already counted
+        let kol_mint = ctx.accounts.kol_mint.key();
+        let distributor = &mut ctx.accounts.distributor;
+        let reserve = distributor
+            .reserves
+            .iter_mut()
+            .find(|reserve| reserve.mint == kol_mint)
+            .ok_or(ErrorCode::KolMintMismatch)?;
+        let claimed = reserve.claimed.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(claimed <= reserve.total, ErrorCode::DistributorReserveExceeded);
+        reserve.claimed = claimed;
+
+        let kol_record = &mut ctx.accounts.kol_record;
+        kol_record.claimed = kol_record.claimed.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+        distributor.set_claimed(index);
+        distributor.claimed_count = distributor.claimed_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        distributor.total_claimed = distributor.total_claimed.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global_pack_pool", &[ctx.accounts.global_pack_pool.bump]]];
+        let accounts = TransferChecked {
+            from: ctx.accounts.kol_token_vault.to_account_info(),
+            mint: ctx.accounts.kol_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.global_pack_pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), accounts, signer_seeds);
+        transfer_checked(cpi_ctx, amount, ctx.accounts.kol_mint.decimals)?;
+
+        msg!("Distributor {} leaf {} paid {} {} to {}", ctx.accounts.distributor.id, index, amount, kol, ctx.accounts.user.key());
+        Ok(())
+    }
+}
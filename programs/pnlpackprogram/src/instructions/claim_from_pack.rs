@@ -1,93 +1,65 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked};
-use crate::instructions::pack_reveal::Pack;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken, Create};
+use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenInterface, transfer_checked, TransferChecked};
+use mpl_token_metadata::instructions::BurnNftCpiBuilder;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, Pack, PackStatus, VestingEscrow};
 
+// accounts passed per pack slot, in slot order
+const ACCOUNTS_PER_SLOT: usize = 4;
+const ACCOUNTS_PER_VESTED_SLOT: usize = 5;
+
+// remaining accounts: for every slot [KolRecord (mut), mint, pack ATA (mut), user ATA (mut)],
+// or [KolRecord (mut), mint, pack ATA (mut), vesting escrow (mut), escrow ATA (mut)] when the
+// pack vests, in which case tokens go to the escrow and withdraw_vested releases them
+// packs sold through buy_pack are owned by whoever holds the pack NFT, which is burned by the
+// first claim, the pack_nft_* accounts are only required until then. Untiered packs topped up
+// after a claim are claimed again by the same owner, adding to an existing vesting escrow
 #[derive(Accounts)]
-#[instruction(kol_a: String, kol_b: String, kol_c: String, kol_d: String)]
 pub struct ClaimFromPack<'info> {
+  #[account(
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump,
+    constraint = !global_pack_pool.paused.claims @ ErrorCode::ProgramPaused
+  )]
+  pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
   #[account(
     mut,
-    seeds = [b"pack", kol_a.as_bytes(), kol_b.as_bytes(), kol_c.as_bytes(), kol_d.as_bytes()],
-    bump = pack.bump
+    seeds = [b"pack", pack.id.to_le_bytes().as_ref()],
+    bump = pack.bump,
+    constraint = pack.status == PackStatus::Funded @ ErrorCode::InvalidPackStatus
   )]
   pub pack: Box<Account<'info, Pack>>,
 
   #[account(mut)]
   pub user: Signer<'info>,
 
-  #[account(mut, mint::token_program = token_program)]
-  pub mint_kol_a: Box<InterfaceAccount<'info, Mint>>,
-  #[account(
-    mut,
-    associated_token::mint = mint_kol_a,
-    associated_token::authority = pack,
-    associated_token::token_program = token_program,
-  )]
-  pub pack_kol_a_ta: Box<InterfaceAccount<'info, TokenAccount>>,
-  #[account(
-    init_if_needed,
-    payer = user,
-    associated_token::mint = mint_kol_a,
-    associated_token::authority = user,
-    associated_token::token_program = token_program,
-  )]
-  pub user_kol_a_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+  #[account(mut)]
+  pub pack_nft_mint: Option<Box<Account<'info, anchor_spl::token::Mint>>>,
 
-  #[account(mut, mint::token_program = token_program)]
-  pub mint_kol_b: Box<InterfaceAccount<'info, Mint>>,
-  #[account(
-    mut,
-    associated_token::mint = mint_kol_b,
-    associated_token::authority = pack,
-    associated_token::token_program = token_program,
-  )]
-  pub pack_kol_b_ta: Box<InterfaceAccount<'info, TokenAccount>>,
-  #[account(
-    init_if_needed,
-    payer = user,
-    associated_token::mint = mint_kol_b,
-    associated_token::authority = user,
-    associated_token::token_program = token_program,
-  )]
-  pub user_kol_b_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+  #[account(mut)]
+  pub user_pack_nft_account: Option<Box<Account<'info, TokenAccount>>>,
 
-  #[account(mut, mint::token_program = token_program)]
-  pub mint_kol_c: Box<InterfaceAccount<'info, Mint>>,
-  #[account(
-    mut,
-    associated_token::mint = mint_kol_c,
-    associated_token::authority = pack,
-    associated_token::token_program = token_program,
-  )]
-  pub pack_kol_c_ta: Box<InterfaceAccount<'info, TokenAccount>>,
-  #[account(
-    init_if_needed,
-    payer = user,
-    associated_token::mint = mint_kol_c,
-    associated_token::authority = user,
-    associated_token::token_program = token_program,
-  )]
-  pub user_kol_c_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+  /// CHECK: validated by the token metadata program
+  #[account(mut)]
+  pub pack_nft_metadata: Option<UncheckedAccount<'info>>,
 
-  #[account(mut, mint::token_program = token_program)]
-  pub mint_kol_d: Box<InterfaceAccount<'info, Mint>>,
-  #[account(
-    mut,
-    associated_token::mint = mint_kol_d,
-    associated_token::authority = pack,
-    associated_token::token_program = token_program,
-  )]
-  pub pack_kol_d_ta: Box<InterfaceAccount<'info, TokenAccount>>,
-  #[account(
-    init_if_needed,
-    payer = user,
-    associated_token::mint = mint_kol_d,
-    associated_token::authority = user,
-    associated_token::token_program = token_program,
-  )]
-  pub user_kol_d_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+  /// CHECK: validated by the token metadata program
+  #[account(mut)]
+  pub pack_nft_master_edition: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: validated by the token metadata program
+  #[account(mut)]
+  pub collection_metadata: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: address is checked
+  #[account(address = mpl_token_metadata::ID)]
+  pub token_metadata_program: Option<UncheckedAccount<'info>>,
+
+  pub nft_token_program: Option<Program<'info, Token>>,
 
   pub system_program: Program<'info, System>,
   pub token_program: Interface<'info, TokenInterface>,
@@ -95,76 +67,205 @@ pub struct ClaimFromPack<'info> {
 }
 
 impl<'info> ClaimFromPack<'info> {
-  pub fn handler(ctx: Context<ClaimFromPack>, kol_a: String, kol_b: String, kol_c: String, kol_d: String, amount_per_kol: u64) -> Result<()> {
+  pub fn handler(ctx: Context<'_, '_, 'info, 'info, ClaimFromPack<'info>>) -> Result<()> {
+    match ctx.accounts.pack.nft_mint {
+      Some(nft_mint) => ctx.accounts.burn_pack_nft(nft_mint)?,
+      None => require_keys_eq!(ctx.accounts.pack.owner, ctx.accounts.user.key(), ErrorCode::NotPackOwner),
+    }
+
     let pack = &ctx.accounts.pack;
+    require!(pack.is_fully_funded(), ErrorCode::PackNotFullyFunded);
+    let mut payouts = Vec::with_capacity(pack.slots.len());
+    for slot in pack.slots.iter() {
+      let remaining = slot.allocated.checked_sub(slot.claimed).ok_or(ErrorCode::ClaimExceedsAllocation)?;
+      payouts.push(remaining);
+    }
+    require!(payouts.iter().any(|amount| *amount > 0), ErrorCode::NothingToClaim);
+    let accounts_per_slot = if pack.vesting.is_some() { ACCOUNTS_PER_VESTED_SLOT } else { ACCOUNTS_PER_SLOT };
+    require!(
+      ctx.remaining_accounts.len() == pack.slots.len() * accounts_per_slot,
+      ErrorCode::PackAccountsMismatch
+    );
+    let now = Clock::get()?.unix_timestamp;
+
+    let pack_id = pack.id.to_le_bytes();
     let signer_seeds: &[&[&[u8]]] = &[&[
       b"pack",
-      pack.kol_a.as_bytes(),
-      pack.kol_b.as_bytes(),
-      pack.kol_c.as_bytes(),
-      pack.kol_d.as_bytes(),
+      &pack_id,
       &[pack.bump],
     ]];
 
-    fn xfer<'a, 'info>(
-      token_program: AccountInfo<'info>,
-      from: AccountInfo<'info>,
-      to: AccountInfo<'info>,
-      mint: AccountInfo<'info>,
-      authority: AccountInfo<'info>,
-      signer_seeds: &[&[&[u8]]],
-      amount: u64,
-      decimals: u8,
-    ) -> Result<()> {
-      let accounts = TransferChecked { from, mint, to, authority };
-      let cpi = CpiContext::new_with_signer(token_program, accounts, signer_seeds);
-      transfer_checked(cpi, amount, decimals)
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let token_program_id = ctx.accounts.token_program.key();
+    let pack_key = pack.key();
+    let user_key = ctx.accounts.user.key();
+
+    for ((slot, payout), accounts) in pack.slots.iter().zip(payouts.iter()).zip(ctx.remaining_accounts.chunks(accounts_per_slot)) {
+      let (record_info, mint_info, pack_ta, escrow_info, destination_ta) = match accounts {
+        [record_info, mint_info, pack_ta, user_ta] => (record_info, mint_info, pack_ta, None, user_ta),
+        [record_info, mint_info, pack_ta, escrow_info, escrow_ta] => (record_info, mint_info, pack_ta, Some(escrow_info), escrow_ta),
+        _ => return err!(ErrorCode::PackAccountsMismatch),
+      };
+      require_keys_eq!(mint_info.key(), slot.mint, ErrorCode::KolMintMismatch);
+      let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+      require!(kol_record.ticker == slot.ticker, ErrorCode::KolRecordMismatch);
+      kol_record.claimed = kol_record.claimed.checked_add(*payout).ok_or(ErrorCode::MathOverflow)?;
+      kol_record.exit(&crate::ID)?;
+      require_keys_eq!(
+        pack_ta.key(),
+        get_associated_token_address_with_program_id(&pack_key, &slot.mint, &token_program_id),
+        ErrorCode::PackAccountsMismatch
+      );
+      let (destination_owner, escrow_bump) = match escrow_info {
+        Some(escrow_info) => {
+          let (escrow, bump) = Pubkey::find_program_address(&[b"vesting", pack_key.as_ref(), slot.mint.as_ref()], &crate::ID);
+          require_keys_eq!(escrow_info.key(), escrow, ErrorCode::PackAccountsMismatch);
+          (escrow, bump)
+        }
+        None => (user_key, 0),
+      };
+      require_keys_eq!(
+        destination_ta.key(),
+        get_associated_token_address_with_program_id(&destination_owner, &slot.mint, &token_program_id),
+        ErrorCode::PackAccountsMismatch
+      );
+
+      if *payout == 0 {
+        continue;
+      }
+
+      associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        Create {
+          payer: ctx.accounts.user.to_account_info(),
+          associated_token: destination_ta.clone(),
+          authority: escrow_info.cloned().unwrap_or_else(|| ctx.accounts.user.to_account_info()),
+          mint: mint_info.clone(),
+          system_program: ctx.accounts.system_program.to_account_info(),
+          token_program: token_program.clone(),
+        },
+      ))?;
+
+      let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+      let accounts = TransferChecked {
+        from: pack_ta.clone(),
+        mint: mint_info.clone(),
+        to: destination_ta.clone(),
+        authority: ctx.accounts.pack.to_account_info(),
+      };
+      let cpi = CpiContext::new_with_signer(token_program.clone(), accounts, signer_seeds);
+      transfer_checked(cpi, *payout, mint.decimals)?;
+
+      if let (Some(escrow_info), Some(schedule)) = (escrow_info, pack.vesting) {
+        // a later claim of a topped-up pack vests alongside the earlier one
+        if escrow_info.owner == &crate::ID {
+          let mut escrow = Account::<VestingEscrow>::try_from(escrow_info)?;
+          escrow.total = escrow.total.checked_add(*payout).ok_or(ErrorCode::MathOverflow)?;
+          escrow.exit(&crate::ID)?;
+          continue;
+        }
+        let escrow = VestingEscrow {
+          bump: escrow_bump,
+          user: user_key,
+          pack: pack_key,
+          mint: slot.mint,
+          total: *payout,
+          withdrawn: 0,
+          start_ts: now,
+          schedule,
+        };
+        let seeds: &[&[u8]] = &[b"vesting", pack_key.as_ref(), slot.mint.as_ref(), &[escrow_bump]];
+        create_pda_account(
+          &ctx.accounts.user.to_account_info(),
+          escrow_info,
+          &ctx.accounts.system_program.to_account_info(),
+          8 + VestingEscrow::INIT_SPACE,
+          seeds,
+        )?;
+        escrow.try_serialize(&mut &mut escrow_info.try_borrow_mut_data()?[..])?;
+      }
+    }
+
+    let pack = &mut ctx.accounts.pack;
+    pack.owner = user_key;
+    // the NFT is burned, ownership stays with the claimer
+    pack.nft_mint = None;
+    for (slot, payout) in pack.slots.iter_mut().zip(payouts) {
+      slot.claimed = slot.claimed.checked_add(payout).ok_or(ErrorCode::MathOverflow)?;
+      require!(slot.claimed <= slot.allocated, ErrorCode::ClaimExceedsAllocation);
     }
+    pack.status = PackStatus::Claimed;
+    pack.claimed_at = now;
+    msg!("Pack {} claimed by {}", ctx.accounts.pack.key(), ctx.accounts.user.key());
+    Ok(())
+  }
 
-    xfer(
-      ctx.accounts.token_program.to_account_info(),
-      ctx.accounts.pack_kol_a_ta.to_account_info(),
-      ctx.accounts.user_kol_a_ta.to_account_info(),
-      ctx.accounts.mint_kol_a.to_account_info(),
-      ctx.accounts.pack.to_account_info(),
-      signer_seeds,
-      amount_per_kol,
-      ctx.accounts.mint_kol_a.decimals,
-    )?;
-
-    xfer(
-      ctx.accounts.token_program.to_account_info(),
-      ctx.accounts.pack_kol_b_ta.to_account_info(),
-      ctx.accounts.user_kol_b_ta.to_account_info(),
-      ctx.accounts.mint_kol_b.to_account_info(),
-      ctx.accounts.pack.to_account_info(),
-      signer_seeds,
-      amount_per_kol,
-      ctx.accounts.mint_kol_b.decimals,
-    )?;
-
-    xfer(
-      ctx.accounts.token_program.to_account_info(),
-      ctx.accounts.pack_kol_c_ta.to_account_info(),
-      ctx.accounts.user_kol_c_ta.to_account_info(),
-      ctx.accounts.mint_kol_c.to_account_info(),
-      ctx.accounts.pack.to_account_info(),
-      signer_seeds,
-      amount_per_kol,
-      ctx.accounts.mint_kol_c.decimals,
-    )?;
-
-    xfer(
-      ctx.accounts.token_program.to_account_info(),
-      ctx.accounts.pack_kol_d_ta.to_account_info(),
-      ctx.accounts.user_kol_d_ta.to_account_info(),
-      ctx.accounts.mint_kol_d.to_account_info(),
-      ctx.accounts.pack.to_account_info(),
-      signer_seeds,
-      amount_per_kol,
-      ctx.accounts.mint_kol_d.decimals,
-    )?;
+  fn burn_pack_nft(&self, nft_mint: Pubkey) -> Result<()> {
+    let (
+      Some(mint),
+      Some(token_account),
+      Some(metadata),
+      Some(master_edition),
+      Some(collection_metadata),
+      Some(token_metadata_program),
+      Some(nft_token_program),
+    ) = (
+      &self.pack_nft_mint,
+      &self.user_pack_nft_account,
+      &self.pack_nft_metadata,
+      &self.pack_nft_master_edition,
+      &self.collection_metadata,
+      &self.token_metadata_program,
+      &self.nft_token_program,
+    ) else {
+      return err!(ErrorCode::PackNftMismatch);
+    };
+    require_keys_eq!(mint.key(), nft_mint, ErrorCode::PackNftMismatch);
+    require!(
+      token_account.mint == nft_mint && token_account.owner == self.user.key() && token_account.amount == 1,
+      ErrorCode::NotPackOwner
+    );
 
+    let mint_info = mint.to_account_info();
+    let token_account_info = token_account.to_account_info();
+    let user_info = self.user.to_account_info();
+    let nft_token_program = nft_token_program.to_account_info();
+    BurnNftCpiBuilder::new(token_metadata_program)
+      .metadata(metadata)
+      .owner(&user_info)
+      .mint(&mint_info)
+      .token_account(&token_account_info)
+      .master_edition_account(master_edition)
+      .spl_token_program(&nft_token_program)
+      .collection_metadata(Some(collection_metadata))
+      .invoke()?;
     Ok(())
   }
-}
\ No newline at end of file
+}
+
+// like anchor's init, still works if someone sent lamports to the address beforehand
+fn create_pda_account<'info>(
+  payer: &AccountInfo<'info>,
+  target: &AccountInfo<'info>,
+  system_program: &AccountInfo<'info>,
+  space: usize,
+  seeds: &[&[u8]],
+) -> Result<()> {
+  let rent = Rent::get()?.minimum_balance(space);
+  let signer_seeds = &[seeds];
+  if target.lamports() == 0 {
+    let accounts = CreateAccount { from: payer.clone(), to: target.clone() };
+    let cpi = CpiContext::new_with_signer(system_program.clone(), accounts, signer_seeds);
+    return create_account(cpi, rent, space as u64, &crate::ID);
+  }
+
+  let top_up = rent.saturating_sub(target.lamports());
+  if top_up > 0 {
+    let accounts = Transfer { from: payer.clone(), to: target.clone() };
+    transfer(CpiContext::new(system_program.clone(), accounts), top_up)?;
+  }
+  let accounts = Allocate { account_to_allocate: target.clone() };
+  allocate(CpiContext::new_with_signer(system_program.clone(), accounts, signer_seeds), space as u64)?;
+  let accounts = Assign { account_to_assign: target.clone() };
+  assign(CpiContext::new_with_signer(system_program.clone(), accounts, signer_seeds), &crate::ID)
+}
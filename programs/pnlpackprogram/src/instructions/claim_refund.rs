@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{Contribution, GlobalPackPool};
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.claims @ ErrorCode::ProgramPaused
+    )]
+    pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+    #[account(
+        mut,
+        seeds = [b"contribution", global_pack_pool.key().as_ref(), user.key().as_ref()],
+        bump = contribution.bump,
+        has_one = user
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+impl<'info> ClaimRefund<'info> {
+    pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.global_pack_pool;
+        let contribution = &mut ctx.accounts.contribution;
+
+        require!(pool.raise.has_failed(now, pool.total_raised), ErrorCode::RefundUnavailable);
+        require!(!contribution.refunded, ErrorCode::AlreadyRefunded);
+        require!(contribution.lamports > 0, ErrorCode::NothingToRefund);
+
+        // the pool PDA is owned by this program, so lamports can be moved out directly
+        let amount = contribution.lamports;
+        pool.sub_lamports(amount)?;
+        ctx.accounts.user.add_lamports(amount)?;
+
+        contribution.refunded = true;
+        pool.total_refunded = pool.total_refunded.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Refunded {} lamports to {}", amount, contribution.user);
+        Ok(())
+    }
+}
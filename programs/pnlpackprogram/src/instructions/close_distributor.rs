@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{Distributor, GlobalPackPool, KolRecord};
+
+// retires a distributor: whatever its leaves have not claimed yet is handed back to the KOL
+// vaults and the rent returns to the authority. Unclaimed leaves can't be claimed afterwards
+//
+// remaining accounts: the KolRecord (mut) of every reserve, in reserve order
+#[derive(Accounts)]
+pub struct CloseDistributor<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        address = global_pack_pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"distributor", global_pack_pool.key().as_ref(), distributor.id.to_le_bytes().as_ref()],
+        bump = distributor.bump,
+        close = admin
+    )]
+    pub distributor: Box<Account<'info, Distributor>>,
+}
+
+impl<'info> CloseDistributor<'info> {
+    pub fn handler(ctx: Context<'_, '_, 'info, 'info, CloseDistributor<'info>>) -> Result<()> {
+        let distributor = &ctx.accounts.distributor;
+        require!(ctx.remaining_accounts.len() == distributor.reserves.len(), ErrorCode::KolRecordMismatch);
+
+        for (reserve, record_info) in distributor.reserves.iter().zip(ctx.remaining_accounts.iter()) {
+            let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+            require_keys_eq!(kol_record.mint, reserve.mint, ErrorCode::KolMintMismatch);
+            let unclaimed = reserve.total.checked_sub(reserve.claimed).ok_or(ErrorCode::MathOverflow)?;
+            kol_record.release(unclaimed)?;
+            kol_record.exit(&crate::ID)?;
+        }
+
+        msg!("Distributor {} closed after {} of {} claims", distributor.id, distributor.claimed_count, distributor.num_leaves);
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::errors::ErrorCode;
+use crate::state::{KolRecord, Pack, PackStatus};
+
+// accounts passed per pack slot, in slot order
+const ACCOUNTS_PER_SLOT: usize = 4;
+
+// permissionless cleanup once a pack is fully claimed, all rent goes back to pack.payer
+//
+// remaining accounts: for every slot [KolRecord (mut), mint, pack ATA (mut), KOL vault (mut)];
+// slots that were never funded have no ATA and are skipped. Tokens still sitting in a pack ATA
+// (sent there after the claim) are returned to the KOL vault so the ATA can always be closed
+#[derive(Accounts)]
+pub struct ClosePack<'info> {
+  #[account(
+    mut,
+    seeds = [b"pack", pack.id.to_le_bytes().as_ref()],
+    bump = pack.bump,
+    constraint = pack.status == PackStatus::Claimed @ ErrorCode::InvalidPackStatus,
+    close = payer
+  )]
+  pub pack: Box<Account<'info, Pack>>,
+
+  #[account(
+    mut,
+    address = pack.payer @ ErrorCode::Unauthorized
+  )]
+  pub payer: SystemAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClosePack<'info> {
+  pub fn handler(ctx: Context<'_, '_, 'info, 'info, ClosePack<'info>>) -> Result<()> {
+    let pack = &ctx.accounts.pack;
+    require!(
+      ctx.remaining_accounts.len() == pack.slots.len() * ACCOUNTS_PER_SLOT,
+      ErrorCode::PackAccountsMismatch
+    );
+
+    let pack_id = pack.id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+      b"pack",
+      &pack_id,
+      &[pack.bump],
+    ]];
+
+    let pack_key = pack.key();
+    let token_program_id = ctx.accounts.token_program.key();
+    for (slot, accounts) in pack.slots.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_SLOT)) {
+      let [record_info, mint_info, pack_ta, vault_info] = accounts else {
+        return err!(ErrorCode::PackAccountsMismatch);
+      };
+      require_keys_eq!(mint_info.key(), slot.mint, ErrorCode::KolMintMismatch);
+      require_keys_eq!(
+        pack_ta.key(),
+        get_associated_token_address_with_program_id(&pack_key, &slot.mint, &token_program_id),
+        ErrorCode::PackAccountsMismatch
+      );
+      if pack_ta.data_is_empty() {
+        continue;
+      }
+
+      let leftover = InterfaceAccount::<TokenAccount>::try_from(pack_ta)?.amount;
+      if leftover > 0 {
+        let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+        require!(kol_record.ticker == slot.ticker, ErrorCode::KolRecordMismatch);
+        require_keys_eq!(vault_info.key(), kol_record.vault, ErrorCode::KolMintMismatch);
+
+        let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+        let accounts = TransferChecked {
+          from: pack_ta.clone(),
+          mint: mint_info.clone(),
+          to: vault_info.clone(),
+          authority: ctx.accounts.pack.to_account_info(),
+        };
+        transfer_checked(
+          CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), accounts, signer_seeds),
+          leftover,
+          mint.decimals,
+        )?;
+
+        kol_record.deposited = kol_record.deposited.checked_add(leftover).ok_or(ErrorCode::MathOverflow)?;
+        kol_record.exit(&crate::ID)?;
+      }
+
+      let accounts = CloseAccount {
+        account: pack_ta.clone(),
+        destination: ctx.accounts.payer.to_account_info(),
+        authority: ctx.accounts.pack.to_account_info(),
+      };
+      close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        accounts,
+        signer_seeds,
+      ))?;
+    }
+
+    msg!("Pack {} closed, rent returned to {}", pack_key, ctx.accounts.payer.key());
+    Ok(())
+  }
+}
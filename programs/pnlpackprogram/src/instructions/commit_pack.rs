@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_KOLS_PER_PACK, MIN_KOLS_PER_PACK, REVEAL_DELAY_SLOTS};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, Pack, PackStatus, Role, RoleAssignment};
+
+// creates an unopened pack bound to a future slot, its KOLs are drawn later by pack_reveal
+#[derive(Accounts)]
+pub struct CommitPack<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.reveal @ ErrorCode::ProgramPaused
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Operator) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Pack::INIT_SPACE,
+        seeds = [b"pack", global_pack_pool.pack_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pack_account: Box<Account<'info, Pack>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitPack<'info> {
+    // pass Pubkey::default() as owner to list the pack for buy_pack
+    pub fn handler(ctx: Context<CommitPack>, owner: Pubkey, size: u8) -> Result<()> {
+        require!((MIN_KOLS_PER_PACK..=MAX_KOLS_PER_PACK).contains(&size), ErrorCode::InvalidPackSize);
+
+        let pool = &mut ctx.accounts.global_pack_pool;
+        // the draw is limited to the registry as it is now, a bigger pack could never be revealed
+        require!(size as usize <= pool.kol_registry.len(), ErrorCode::NotEnoughKols);
+        let pack = &mut ctx.accounts.pack_account;
+
+        pack.id = pool.pack_count;
+        pack.bump = ctx.bumps.pack_account;
+        pack.owner = owner;
+        pack.payer = ctx.accounts.admin.key();
+        pack.reveal_slot = Clock::get()?.slot.checked_add(REVEAL_DELAY_SLOTS).ok_or(ErrorCode::MathOverflow)?;
+        pack.status = PackStatus::Created;
+        pack.size = size;
+        pack.registry_len = pool.kol_registry.len() as u16;
+
+        // a listed pack only waits on a reveal once buy_pack has committed it again
+        if owner != Pubkey::default() {
+            pool.unrevealed_packs = pool.unrevealed_packs.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+        pool.pack_count = pool.pack_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("🔵 [CommitPack] Pack {} of {} KOLs committed to slot {}", pack.id, pack.size, pack.reveal_slot);
+        Ok(())
+    }
+}
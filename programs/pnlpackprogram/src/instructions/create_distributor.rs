@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_DISTRIBUTOR_KOLS, MAX_DISTRIBUTOR_LEAVES};
+use crate::errors::ErrorCode;
+use crate::state::{Distributor, DistributorReserve, GlobalPackPool, KolRecord};
+
+// publishes a merkle root of (user, kol, amount) allocations, claimed with claim_from_distributor.
+// totals[i] is the sum of the leaf amounts for the KOL of remaining account i, it is reserved
+// in that KOL's vault up front so packs can't spend what the leaves are owed
+//
+// remaining accounts: the KolRecord (mut) of every KOL in the tree, in totals order
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], num_leaves: u32)]
+pub struct CreateDistributor<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        address = global_pack_pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Distributor::space(num_leaves.min(MAX_DISTRIBUTOR_LEAVES)),
+        seeds = [b"distributor", global_pack_pool.key().as_ref(), global_pack_pool.distributor_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distributor: Box<Account<'info, Distributor>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateDistributor<'info> {
+    pub fn handler(
+        ctx: Context<'_, '_, 'info, 'info, CreateDistributor<'info>>,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        totals: Vec<u64>,
+    ) -> Result<()> {
+        require!((1..=MAX_DISTRIBUTOR_LEAVES).contains(&num_leaves), ErrorCode::InvalidDistributorConfig);
+        require!((1..=MAX_DISTRIBUTOR_KOLS).contains(&totals.len()), ErrorCode::InvalidDistributorConfig);
+        require!(ctx.remaining_accounts.len() == totals.len(), ErrorCode::KolRecordMismatch);
+
+        let mut reserves: Vec<DistributorReserve> = Vec::with_capacity(totals.len());
+        for (record_info, total) in ctx.remaining_accounts.iter().zip(totals) {
+            let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+            require!(
+                !reserves.iter().any(|reserve| reserve.mint == kol_record.mint),
+                ErrorCode::InvalidDistributorConfig
+            );
+            kol_record.allocate(total)?;
+            kol_record.exit(&crate::ID)?;
+            reserves.push(DistributorReserve { mint: kol_record.mint, total, claimed: 0 });
+        }
+
+        let pool = &mut ctx.accounts.global_pack_pool;
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.id = pool.distributor_count;
+        distributor.bump = ctx.bumps.distributor;
+        distributor.merkle_root = merkle_root;
+        distributor.num_leaves = num_leaves;
+        distributor.reserves = reserves;
+        distributor.claimed_bitmap = vec![0; (num_leaves as usize).div_ceil(8)];
+
+        pool.distributor_count = pool.distributor_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Distributor {} created with {} leaves", distributor.id, num_leaves);
+        Ok(())
+    }
+}
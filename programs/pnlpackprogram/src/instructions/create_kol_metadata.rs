@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::Mint;
+use mpl_token_metadata::instructions::CreateMetadataAccountV3CpiBuilder;
+use mpl_token_metadata::types::DataV2;
+use crate::constants::{MAX_NFT_SYMBOL_LEN, MAX_NFT_URI_LEN};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, Role, RoleAssignment};
+
+// gives the KOL mint a name, symbol and image so wallets can display it, the pool PDA
+// becomes update authority so update_kol_metadata can change it later
+#[derive(Accounts)]
+#[instruction(kol_ticker: String)]
+pub struct CreateKolMetadata<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+    #[account(
+        mut,
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Minter) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+        bump = kol_record.bump
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
+    #[account(
+        address = kol_record.mint @ ErrorCode::KolMintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: address is checked
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// name comes from the KOL display name, the symbol is passed separately because tickers
+// may be longer than the metadata program's 10 byte symbol limit
+pub(crate) fn kol_metadata(kol_record: &KolRecord, symbol: String, uri: String) -> Result<DataV2> {
+    require!(
+        symbol.len() <= MAX_NFT_SYMBOL_LEN && uri.len() <= MAX_NFT_URI_LEN,
+        ErrorCode::NftMetadataTooLong
+    );
+    Ok(DataV2 {
+        name: kol_record.name.clone(),
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    })
+}
+
+impl<'info> CreateKolMetadata<'info> {
+    pub fn handler(ctx: Context<CreateKolMetadata>, kol_ticker: String, symbol: String, uri: String) -> Result<()> {
+        let data = kol_metadata(&ctx.accounts.kol_record, symbol, uri)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global_pack_pool", &[ctx.accounts.global_pack_pool.bump]]];
+        let pool_info = ctx.accounts.global_pack_pool.to_account_info();
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let admin_info = ctx.accounts.admin.to_account_info();
+        // the admin until lock_kol_supply hands the mint to the pool, a revoked mint can't get metadata
+        let mint_authority = if ctx.accounts.mint.mint_authority == COption::Some(pool_info.key()) {
+            &pool_info
+        } else {
+            &admin_info
+        };
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let rent = ctx.accounts.rent.to_account_info();
+
+        CreateMetadataAccountV3CpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .metadata(&ctx.accounts.metadata)
+            .mint(&mint_info)
+            .mint_authority(mint_authority)
+            .payer(&admin_info)
+            .update_authority(&pool_info, true)
+            .system_program(&system_program)
+            .rent(Some(&rent))
+            .data(data)
+            .is_mutable(true)
+            .invoke_signed(signer_seeds)?;
+
+        msg!("Metadata created for KOL {}: {}", kol_ticker, ctx.accounts.metadata.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord};
+
+// permissionless top-up of a KOL vault, counted as deposited so packs and distributors
+// can allocate it. Tokens sent to the vault any other way are not allocatable
+#[derive(Accounts)]
+#[instruction(kol_ticker: String)]
+pub struct DepositToKolVault<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+        bump = kol_record.bump,
+        constraint = kol_record.mint == kol_mint.key() @ ErrorCode::KolMintMismatch,
+        constraint = kol_record.vault == kol_token_vault.key() @ ErrorCode::KolMintMismatch
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub kol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = kol_mint,
+        token::authority = global_pack_pool,
+        token::token_program = token_program
+    )]
+    pub kol_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub depositor: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = kol_mint,
+        token::authority = depositor,
+        token::token_program = token_program
+    )]
+    pub depositor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DepositToKolVault<'info> {
+    pub fn handler(ctx: Context<DepositToKolVault>, kol_ticker: String, amount: u64) -> Result<()> {
+        let accounts = TransferChecked {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            mint: ctx.accounts.kol_mint.to_account_info(),
+            to: ctx.accounts.kol_token_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), accounts);
+        transfer_checked(cpi_ctx, amount, ctx.accounts.kol_mint.decimals)?;
+
+        let kol_record = &mut ctx.accounts.kol_record;
+        kol_record.deposited = kol_record.deposited.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Deposited {} {} into the vault, {} deposited in total", amount, kol_ticker, kol_record.deposited);
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, Role, RoleAssignment};
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct GrantRole<'info> {
+  #[account(
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    mut,
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+
+  #[account(
+    init_if_needed,
+    payer = admin,
+    space = 8 + RoleAssignment::INIT_SPACE,
+    seeds = [b"role", global_pack_pool.key().as_ref(), member.as_ref()],
+    bump
+  )]
+  pub role_assignment: Account<'info, RoleAssignment>,
+
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> GrantRole<'info> {
+  pub fn handler(ctx: Context<GrantRole>, member: Pubkey, role: Role) -> Result<()> {
+    require!(role != Role::Authority, ErrorCode::InvalidRole);
+
+    let assignment = &mut ctx.accounts.role_assignment;
+    assignment.bump = ctx.bumps.role_assignment;
+    assignment.member = member;
+    assignment.roles |= role.bit();
+    msg!("Granted {:?} to {}", role, member);
+    Ok(())
+  }
+}
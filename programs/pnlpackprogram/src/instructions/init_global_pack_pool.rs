@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::constants::ADMIN_KEY;
-use crate::state::GlobalPackPool;
+use crate::constants::{ADMIN_KEY, MAX_KOLS};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, RaiseConfig};
 
 #[derive(Accounts)]
 pub struct InitGlobalPackPool<'info> {
@@ -23,8 +24,16 @@ pub struct InitGlobalPackPool<'info> {
 }
 
 impl<'info> InitGlobalPackPool<'info> {
-  pub fn handler(_ctx: Context<InitGlobalPackPool>, total_kols: u8) -> Result<()> {
-    _ctx.accounts.global_pack_pool.total_kols = total_kols; 
+  pub fn handler(ctx: Context<InitGlobalPackPool>, total_kols: u8, raise: RaiseConfig) -> Result<()> {
+    require!(raise.is_valid(), ErrorCode::InvalidRaiseConfig);
+    require!(total_kols as usize <= MAX_KOLS, ErrorCode::TooManyKols);
+
+    let pool = &mut ctx.accounts.global_pack_pool;
+    pool.bump = ctx.bumps.global_pack_pool;
+    pool.total_kols = total_kols;
+    pool.authority = ctx.accounts.admin.key();
+    pool.pending_authority = None;
+    pool.raise = raise;
     Ok(())
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, Leaderboard};
+
+// opens a scoring season over an inclusive range of oracle epochs
+#[derive(Accounts)]
+#[instruction(season: u32)]
+pub struct InitLeaderboard<'info> {
+  #[account(
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    mut,
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+
+  #[account(
+    init,
+    payer = admin,
+    space = 8 + Leaderboard::INIT_SPACE,
+    seeds = [b"leaderboard", global_pack_pool.key().as_ref(), season.to_le_bytes().as_ref()],
+    bump
+  )]
+  pub leaderboard: Account<'info, Leaderboard>,
+
+  pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitLeaderboard<'info> {
+  pub fn handler(ctx: Context<InitLeaderboard>, season: u32, start_epoch: u64, end_epoch: u64) -> Result<()> {
+    require!(start_epoch <= end_epoch, ErrorCode::InvalidSeason);
+
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.bump = ctx.bumps.leaderboard;
+    leaderboard.season = season;
+    leaderboard.start_epoch = start_epoch;
+    leaderboard.end_epoch = end_epoch;
+    msg!("Season {} opened for epochs {}..={}", season, start_epoch, end_epoch);
+    Ok(())
+  }
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+use mpl_token_metadata::instructions::{CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder};
+use mpl_token_metadata::types::{CollectionDetails, DataV2};
+use crate::constants::{MAX_NFT_SYMBOL_LEN, MAX_NFT_URI_LEN, MAX_PACK_NFT_NAME_LEN};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, PackCollection};
+
+// mints the sized collection NFT that every pack NFT is verified into, the pool PDA
+// is mint, update and collection authority
+#[derive(Accounts)]
+pub struct InitPackCollection<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = global_pack_pool.pack_collection.is_none() @ ErrorCode::PackCollectionAlreadyInitialized
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        address = global_pack_pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"pack_collection"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = global_pack_pool,
+        mint::freeze_authority = global_pack_pool,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = collection_mint,
+        associated_token::authority = global_pack_pool,
+    )]
+    pub collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), collection_mint.key().as_ref(), b"edition"],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: address is checked
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> InitPackCollection<'info> {
+    pub fn handler(ctx: Context<InitPackCollection>, name: String, symbol: String, uri: String) -> Result<()> {
+        require!(
+            name.len() <= MAX_PACK_NFT_NAME_LEN && symbol.len() <= MAX_NFT_SYMBOL_LEN && uri.len() <= MAX_NFT_URI_LEN,
+            ErrorCode::NftMetadataTooLong
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global_pack_pool", &[ctx.accounts.global_pack_pool.bump]]];
+        let pool_info = ctx.accounts.global_pack_pool.to_account_info();
+        let mint_info = ctx.accounts.collection_mint.to_account_info();
+        let admin_info = ctx.accounts.admin.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let rent = ctx.accounts.rent.to_account_info();
+
+        mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.collection_token_account.to_account_info(),
+                    authority: pool_info.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+
+        CreateMetadataAccountV3CpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .metadata(&ctx.accounts.collection_metadata)
+            .mint(&mint_info)
+            .mint_authority(&pool_info)
+            .payer(&admin_info)
+            .update_authority(&pool_info, true)
+            .system_program(&system_program)
+            .rent(Some(&rent))
+            .data(DataV2 {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            })
+            .is_mutable(true)
+            .collection_details(CollectionDetails::V1 { size: 0 })
+            .invoke_signed(signer_seeds)?;
+
+        CreateMasterEditionV3CpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .edition(&ctx.accounts.collection_master_edition)
+            .mint(&mint_info)
+            .update_authority(&pool_info)
+            .mint_authority(&pool_info)
+            .payer(&admin_info)
+            .metadata(&ctx.accounts.collection_metadata)
+            .token_program(&token_program)
+            .system_program(&system_program)
+            .rent(Some(&rent))
+            .max_supply(0)
+            .invoke_signed(signer_seeds)?;
+
+        ctx.accounts.global_pack_pool.pack_collection = Some(PackCollection {
+            mint: ctx.accounts.collection_mint.key(),
+            name,
+            symbol,
+            uri,
+        });
+
+        msg!("Pack collection initialized: {}", ctx.accounts.collection_mint.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{set_authority, Mint, SetAuthority, TokenInterface};
+use anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, Role, RoleAssignment};
+
+// takes the mint authority away from the admin so the KOL supply is capped at max_supply,
+// either by revoking it or by handing it to the pool PDA, which never mints
+#[derive(Accounts)]
+#[instruction(kol_ticker: String)]
+pub struct LockKolSupply<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+    #[account(
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Minter) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+        bump = kol_record.bump,
+        constraint = !kol_record.supply_locked @ ErrorCode::KolSupplyLocked
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
+    #[account(
+        mut,
+        address = kol_record.mint @ ErrorCode::KolMintMismatch,
+        mint::authority = admin,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> LockKolSupply<'info> {
+    pub fn handler(ctx: Context<LockKolSupply>, kol_ticker: String, revoke: bool) -> Result<()> {
+        // anything minted after registration would otherwise be locked in silently
+        require!(ctx.accounts.mint.supply == ctx.accounts.kol_record.max_supply, ErrorCode::KolSupplyMismatch);
+
+        let new_authority = if revoke { None } else { Some(ctx.accounts.global_pack_pool.key()) };
+        let cpi = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.admin.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            },
+        );
+        set_authority(cpi, AuthorityType::MintTokens, new_authority)?;
+
+        ctx.accounts.kol_record.supply_locked = true;
+        msg!("KOL {} supply locked at {}", kol_ticker, ctx.accounts.kol_record.max_supply);
+        Ok(())
+    }
+}
@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 use anchor_spl::associated_token::AssociatedToken;
-use crate::constants::ADMIN_KEY;
+use crate::constants::{MAX_KOL_NAME_LEN, MAX_KOL_TICKER_LEN};
+use crate::errors::ErrorCode;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked, mint_to, MintTo};
-use anchor_spl::token_interface;
-use crate::state::GlobalPackPool;
+use crate::state::{GlobalPackPool, KolRecord, KolStatus, Role, RoleAssignment};
 
 #[derive(Accounts)]
 #[instruction(kol_ticker: String)]
@@ -18,10 +17,16 @@ pub struct MintAndInitKolTokenVaultAndTransfer<'info> {
 
     #[account(
         mut,
-        address = ADMIN_KEY
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Minter) @ ErrorCode::Unauthorized
     )]
     pub admin: Signer<'info>,
 
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
 
     #[account(
         mut,
@@ -53,6 +58,16 @@ pub struct MintAndInitKolTokenVaultAndTransfer<'info> {
     )]
     pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Registry entry linking the ticker to its mint and vault
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + KolRecord::INIT_SPACE,
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+        bump
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -60,8 +75,26 @@ pub struct MintAndInitKolTokenVaultAndTransfer<'info> {
 }
 
 impl<'info> MintAndInitKolTokenVaultAndTransfer<'info> {
-    pub fn handler(ctx: Context<Self>, kol_ticker: String, total_supply: u64, vault_transfer_amount: u64) -> Result<()> {
-        
+    pub fn handler(ctx: Context<Self>, kol_ticker: String, display_name: String, total_supply: u64, vault_transfer_amount: u64) -> Result<()> {
+        require!(kol_ticker.len() <= MAX_KOL_TICKER_LEN, ErrorCode::KolTickerTooLong);
+        require!(display_name.len() <= MAX_KOL_NAME_LEN, ErrorCode::KolNameTooLong);
+        let pool = &mut ctx.accounts.global_pack_pool;
+        require!(pool.kol_registry.len() < pool.total_kols as usize, ErrorCode::KolRegistryFull);
+        pool.kol_registry.push(kol_ticker.clone());
+
+        let kol_record = &mut ctx.accounts.kol_record;
+        kol_record.bump = ctx.bumps.kol_record;
+        kol_record.index = pool.kols_registered;
+        kol_record.ticker = kol_ticker.clone();
+        kol_record.name = display_name;
+        kol_record.mint = ctx.accounts.mint.key();
+        kol_record.vault = ctx.accounts.token_vault.key();
+        kol_record.decimals = ctx.accounts.mint.decimals;
+        kol_record.status = KolStatus::Active;
+        kol_record.supply_locked = false;
+        kol_record.deposited = vault_transfer_amount;
+        pool.kols_registered = pool.kols_registered.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
         let decimals = ctx.accounts.mint.decimals;
         
         // minting 1B to admin
@@ -81,6 +114,10 @@ impl<'info> MintAndInitKolTokenVaultAndTransfer<'info> {
         mint_to(mint_cpi_ctx, total_supply)?;
 
         msg!("Successfully minted {} tokens to admin", total_supply);
+
+        // the supply lock_kol_supply will freeze
+        ctx.accounts.mint.reload()?;
+        ctx.accounts.kol_record.max_supply = ctx.accounts.mint.supply;
         
 
         msg!("Token vault initialized for KOL: {} with mint: {}", 
@@ -7,6 +7,9 @@ pub use transfer_to_pack_pool::*;
 pub mod mint_and_init_kol_token_vault_and_transfer;
 pub use mint_and_init_kol_token_vault_and_transfer::*;
 
+pub mod commit_pack;
+pub use commit_pack::*;
+
 pub mod pack_reveal;
 pub use pack_reveal::*;
 
@@ -14,4 +17,94 @@ pub mod transfer_to_individual_pack;
 pub use transfer_to_individual_pack::*;
 
 pub mod claim_from_pack;
-pub use claim_from_pack::*;
\ No newline at end of file
+pub use claim_from_pack::*;
+
+pub mod propose_authority;
+pub use propose_authority::*;
+
+pub mod accept_authority;
+pub use accept_authority::*;
+
+pub mod claim_refund;
+pub use claim_refund::*;
+
+pub mod set_fee_recipients;
+pub use set_fee_recipients::*;
+
+pub mod withdraw_treasury;
+pub use withdraw_treasury::*;
+
+pub mod set_pack_price;
+pub use set_pack_price::*;
+
+pub mod buy_pack;
+pub use buy_pack::*;
+
+pub mod set_kol_status;
+pub use set_kol_status::*;
+
+pub mod set_pack_tiers;
+pub use set_pack_tiers::*;
+
+pub mod close_pack;
+pub use close_pack::*;
+
+pub mod init_pack_collection;
+pub use init_pack_collection::*;
+
+pub mod create_kol_metadata;
+pub use create_kol_metadata::*;
+
+pub mod update_kol_metadata;
+pub use update_kol_metadata::*;
+
+pub mod lock_kol_supply;
+pub use lock_kol_supply::*;
+
+pub mod batch_transfer_to_packs;
+pub use batch_transfer_to_packs::*;
+
+pub mod create_distributor;
+pub use create_distributor::*;
+
+pub mod claim_from_distributor;
+pub use claim_from_distributor::*;
+
+pub mod set_vesting;
+pub use set_vesting::*;
+
+pub mod withdraw_vested;
+pub use withdraw_vested::*;
+
+pub mod set_pause_flags;
+pub use set_pause_flags::*;
+
+pub mod grant_role;
+pub use grant_role::*;
+
+pub mod revoke_role;
+pub use revoke_role::*;
+
+pub mod set_max_pnl_delta;
+pub use set_max_pnl_delta::*;
+
+pub mod record_kol_pnl;
+pub use record_kol_pnl::*;
+
+pub mod init_leaderboard;
+pub use init_leaderboard::*;
+
+pub mod score_pack;
+pub use score_pack::*;
+
+pub mod deposit_to_kol_vault;
+pub use deposit_to_kol_vault::*;
+
+pub mod cancel_pack;
+pub use cancel_pack::*;
+
+pub mod recommit_pack;
+pub use recommit_pack::*;
+
+pub mod close_distributor;
+pub use close_distributor::*;
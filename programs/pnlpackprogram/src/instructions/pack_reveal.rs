@@ -1,139 +1,116 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use anchor_spl::associated_token::AssociatedToken;
-use crate::constants::ADMIN_KEY;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use anchor_spl::token_interface;
-use crate::state::GlobalPackPool;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use crate::errors::ErrorCode;
+use crate::events::PackRevealed;
+use crate::randomness::{derive_seed, draw_distinct, roll_weighted, slot_hash_at};
+use crate::state::{GlobalPackPool, KolRecord, Pack, PackSlot, PackStatus};
 
+// permissionless: the outcome is fixed by the committed slot hash, so anyone (usually the
+// pack owner) can reveal and nobody can hold a pack back until its slot hash expires
+//
+// remaining accounts: the KolRecords (mut) of the drawn KOLs, in slot order
 #[derive(Accounts)]
-#[instruction(kol_a: String, kol_b: String, kol_c: String, kol_d: String)]
 pub struct PackReveal<'info> {
     #[account(
         mut,
         seeds = [b"global_pack_pool"],
-        bump
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.reveal @ ErrorCode::ProgramPaused
     )]
     pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
 
     #[account(
         mut,
-        address = ADMIN_KEY
-    )]
-    pub admin: Signer<'info>,
-    
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + std::mem::size_of::<Pack>(),
-        seeds = [
-            b"pack",
-            kol_a.as_bytes(),
-            kol_b.as_bytes(),
-            kol_c.as_bytes(),
-            kol_d.as_bytes()
-        ],
-        bump
+        seeds = [b"pack", pack_account.id.to_le_bytes().as_ref()],
+        bump = pack_account.bump,
+        constraint = pack_account.status == PackStatus::Created @ ErrorCode::PackAlreadyRevealed,
+        // listed packs stay sealed until buy_pack commits them to a slot after the sale
+        constraint = pack_account.owner != Pubkey::default() @ ErrorCode::PackNotSold
     )]
     pub pack_account: Box<Account<'info, Pack>>,
 
-    #[account(
-        mut,
-        mint::token_program = token_program,
-    )]
-    pub mint_kol_a: Box<InterfaceAccount<'info, Mint>>,
-
-    #[account(
-        init,
-        payer = admin,
-        associated_token::mint = mint_kol_a,
-        associated_token::authority = pack_account,
-        associated_token::token_program = token_program,
-    )]
-    pub pack_kol_a_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: address is checked, the data is parsed by randomness::slot_hash_at
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
 
-    #[account(
-        mut,
-        mint::token_program = token_program,
-    )]
-    pub mint_kol_b: Box<InterfaceAccount<'info, Mint>>,
+impl<'info> PackReveal<'info> {
+    pub fn handler(ctx: Context<'_, '_, 'info, 'info, PackReveal<'info>>) -> Result<()> {
+        let current_slot = Clock::get()?.slot;
+        let pack = &mut ctx.accounts.pack_account;
+        // KOLs registered after the commit can't be drawn, removals are blocked while packs are unrevealed
+        let registry = &ctx.accounts.global_pack_pool.kol_registry[..pack.registry_len as usize];
+        let size = pack.size as usize;
+        require!(registry.len() >= size, ErrorCode::NotEnoughKols);
+        require!(current_slot > pack.reveal_slot, ErrorCode::RandomnessNotReady);
 
-    #[account(
-        init,
-        payer = admin,
-        associated_token::mint = mint_kol_b,
-        associated_token::authority = pack_account,
-        associated_token::token_program = token_program,
-    )]
-    pub pack_kol_b_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+        // once the committed slot rotates out of SlotHashes only recommit_pack can pick a new one,
+        // otherwise a holder could wait out an outcome they don't like and roll again
+        let slot_hash = slot_hash_at(&ctx.accounts.slot_hashes, pack.reveal_slot)?.ok_or(ErrorCode::RevealSlotExpired)?;
 
-    #[account(
-        mut,
-        mint::token_program = token_program,
-    )]
-    pub mint_kol_c: Box<InterfaceAccount<'info, Mint>>,
+        let seed = derive_seed(&slot_hash, &pack.key(), pack.id);
+        let drawn = draw_distinct(&seed, registry.len(), size);
 
-    #[account(
-        init,
-        payer = admin,
-        associated_token::mint = mint_kol_c,
-        associated_token::authority = pack_account,
-        associated_token::token_program = token_program,
-    )]
-    pub pack_kol_c_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+        // roll the tier, then each slot's amount from that tier's table for the slot position
+        let tiers = &ctx.accounts.global_pack_pool.tiers;
+        let tier = if tiers.is_empty() {
+            None
+        } else {
+            let weights: Vec<u16> = tiers.iter().map(|tier| tier.weight).collect();
+            let tier = &tiers[roll_weighted(&seed, b"tier", &weights)];
+            require!(tier.slot_tables.len() >= size, ErrorCode::InvalidTierConfig);
+            Some(tier)
+        };
+        let targets: Vec<u64> = (0..size)
+            .map(|i| match tier {
+                Some(tier) => {
+                    let outcomes = &tier.slot_tables[i].outcomes;
+                    let weights: Vec<u16> = outcomes.iter().map(|outcome| outcome.weight).collect();
+                    let domain = [b"slot".as_ref(), &(i as u64).to_le_bytes()].concat();
+                    outcomes[roll_weighted(&seed, &domain, &weights)].amount
+                }
+                None => 0,
+            })
+            .collect();
 
-    #[account(
-        mut,
-        mint::token_program = token_program,
-    )]
-    pub mint_kol_d: Box<InterfaceAccount<'info, Mint>>,
+        require!(ctx.remaining_accounts.len() == size, ErrorCode::KolRecordMismatch);
+        let mut slots = Vec::with_capacity(size);
+        for ((index, record_info), target) in drawn.iter().zip(ctx.remaining_accounts.iter()).zip(targets) {
+            let mut kol_record = Account::<KolRecord>::try_from(record_info)?;
+            require!(kol_record.ticker == registry[*index], ErrorCode::KolRecordMismatch);
+            // tiered targets are reserved up front so funding can't run the vault dry
+            kol_record.allocate(target)?;
+            kol_record.exit(&crate::ID)?;
+            slots.push(PackSlot {
+                ticker: kol_record.ticker.clone(),
+                mint: kol_record.mint,
+                target,
+                allocated: 0,
+                claimed: 0,
+            });
+        }
 
-    #[account(
-        init,
-        payer = admin,
-        associated_token::mint = mint_kol_d,
-        associated_token::authority = pack_account,
-        associated_token::token_program = token_program,
-    )]
-    pub pack_kol_d_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+        pack.slots = slots;
+        pack.tier = tier.map(|tier| tier.tier);
+        pack.vesting = tier.and_then(|tier| tier.vesting).or(ctx.accounts.global_pack_pool.vesting);
+        pack.randomness_seed = seed;
+        pack.status = PackStatus::Revealed;
 
-    pub system_program: Program<'info, System>,
-    pub token_program: Interface<'info, TokenInterface>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-}
+        let pool = &mut ctx.accounts.global_pack_pool;
+        pool.unrevealed_packs = pool.unrevealed_packs.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
 
-impl<'info> PackReveal<'info> {
-    pub fn handler(
-        ctx: Context<PackReveal>, 
-        kol_a: String,
-        kol_b: String, 
-        kol_c: String,
-        kol_d: String,
-    ) -> Result<()> {
-        msg!("🔵 [PackReveal] Initializing Pack data for KOLs: {}, {}, {}, {}", kol_a, kol_b, kol_c, kol_d);
-        let pack = &mut ctx.accounts.pack_account;
-        pack.kol_a = kol_a.clone();
-        pack.kol_b = kol_b.clone();
-        pack.kol_c = kol_c.clone();
-        pack.kol_d = kol_d.clone();
-        pack.bump = ctx.bumps.pack_account;
+        let kols: Vec<String> = pack.slots.iter().map(|slot| slot.ticker.clone()).collect();
+        msg!("🟢 [PackReveal] Pack {} revealed: {:?}, tier {:?}", pack.id, kols, pack.tier);
 
-        msg!("🟢 [PackReveal] Pack struct initialized: kol_a={}, kol_b={}, kol_c={}, kol_d={}, bump={}", pack.kol_a, pack.kol_b, pack.kol_c, pack.kol_d, pack.bump);
-        msg!("✅ [PackReveal] Pack accounts and ATAs initialized");
+        emit!(PackRevealed {
+            pack: pack.key(),
+            id: pack.id,
+            reveal_slot: pack.reveal_slot,
+            slot_hash,
+            randomness_seed: seed,
+            tier: pack.tier,
+            kols,
+        });
         Ok(())
     }
 }
-
-#[account]
-#[derive(InitSpace)]
-pub struct Pack {
-    #[max_len(16)]
-    pub kol_a: String,
-    #[max_len(16)]
-    pub kol_b: String,
-    #[max_len(16)]
-    pub kol_c: String,
-    #[max_len(16)]
-    pub kol_d: String,
-    pub bump: u8,
-}
\ No newline at end of file
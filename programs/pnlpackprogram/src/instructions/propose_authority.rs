@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::GlobalPackPool;
+
+// step 1 of the authority handover: the current authority nominates a successor
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> ProposeAuthority<'info> {
+  pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.global_pack_pool.pending_authority = Some(new_authority);
+    msg!("Proposed new authority: {}", new_authority);
+    Ok(())
+  }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use crate::constants::REVEAL_DELAY_SLOTS;
+use crate::errors::ErrorCode;
+use crate::events::PackRecommitted;
+use crate::randomness::slot_hash_at;
+use crate::state::{GlobalPackPool, Pack, PackStatus, Role, RoleAssignment};
+
+// moves a pack whose committed slot hash expired before anyone revealed it to a fresh slot.
+// operator-only so the holder, who can already see the expired outcome, can't roll again
+#[derive(Accounts)]
+pub struct RecommitPack<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump,
+        constraint = !global_pack_pool.paused.reveal @ ErrorCode::ProgramPaused
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Operator) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"pack", pack_account.id.to_le_bytes().as_ref()],
+        bump = pack_account.bump,
+        constraint = pack_account.status == PackStatus::Created @ ErrorCode::PackAlreadyRevealed,
+        constraint = pack_account.owner != Pubkey::default() @ ErrorCode::PackNotSold
+    )]
+    pub pack_account: Box<Account<'info, Pack>>,
+
+    /// CHECK: address is checked, the data is parsed by randomness::slot_hash_at
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+impl<'info> RecommitPack<'info> {
+    pub fn handler(ctx: Context<RecommitPack>) -> Result<()> {
+        let current_slot = Clock::get()?.slot;
+        let pack = &mut ctx.accounts.pack_account;
+        require!(current_slot > pack.reveal_slot, ErrorCode::RandomnessNotReady);
+        require!(
+            slot_hash_at(&ctx.accounts.slot_hashes, pack.reveal_slot)?.is_none(),
+            ErrorCode::RevealSlotNotExpired
+        );
+
+        pack.reveal_slot = current_slot.checked_add(REVEAL_DELAY_SLOTS).ok_or(ErrorCode::MathOverflow)?;
+        pack.recommits = pack.recommits.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("🟡 [RecommitPack] Pack {} re-committed to slot {} ({} times)", pack.id, pack.reveal_slot, pack.recommits);
+        emit!(PackRecommitted {
+            pack: pack.key(),
+            id: pack.id,
+            recommits: pack.recommits,
+            reveal_slot: pack.reveal_slot,
+        });
+        Ok(())
+    }
+}
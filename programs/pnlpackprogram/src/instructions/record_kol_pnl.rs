@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::events::KolPnlRecorded;
+use crate::state::{GlobalPackPool, KolPerformance, KolPnlReading, KolRecord, Role, RoleAssignment};
+
+// posts one PnL reading for a KOL; epochs and timestamps must move forward and the
+// value may not jump by more than max_pnl_delta (unless it is 0, i.e. not configured),
+// the first reading is taken as is.
+// every reading is also kept per epoch in a KolPnlReading
+#[derive(Accounts)]
+#[instruction(kol_ticker: String, epoch: u64)]
+pub struct RecordKolPnl<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+    #[account(
+        mut,
+        constraint = global_pack_pool.has_role(&oracle.key(), role.as_deref(), Role::Oracle) @ ErrorCode::Unauthorized
+    )]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), oracle.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+        bump = kol_record.bump
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = 8 + KolPerformance::INIT_SPACE,
+        seeds = [b"kol_performance", kol_record.key().as_ref()],
+        bump
+    )]
+    pub kol_performance: Box<Account<'info, KolPerformance>>,
+
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + KolPnlReading::INIT_SPACE,
+        seeds = [b"kol_pnl", kol_record.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub kol_pnl_reading: Box<Account<'info, KolPnlReading>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordKolPnl<'info> {
+    pub fn handler(ctx: Context<RecordKolPnl>, kol_ticker: String, epoch: u64, pnl: i64, timestamp: i64) -> Result<()> {
+        require!(timestamp <= Clock::get()?.unix_timestamp, ErrorCode::InvalidPnlTimestamp);
+
+        let max_delta = ctx.accounts.global_pack_pool.max_pnl_delta;
+        let performance = &mut ctx.accounts.kol_performance;
+        if performance.readings > 0 {
+            require!(epoch > performance.epoch, ErrorCode::PnlEpochNotIncreasing);
+            require!(timestamp > performance.timestamp, ErrorCode::InvalidPnlTimestamp);
+            require!(max_delta == 0 || pnl.abs_diff(performance.pnl) <= max_delta, ErrorCode::PnlDeltaExceeded);
+        } else {
+            performance.bump = ctx.bumps.kol_performance;
+            performance.kol_record = ctx.accounts.kol_record.key();
+        }
+
+        performance.epoch = epoch;
+        performance.pnl = pnl;
+        performance.timestamp = timestamp;
+        performance.readings = performance.readings.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        let reading = &mut ctx.accounts.kol_pnl_reading;
+        reading.bump = ctx.bumps.kol_pnl_reading;
+        reading.kol_record = performance.kol_record;
+        reading.epoch = epoch;
+        reading.pnl = pnl;
+        reading.timestamp = timestamp;
+
+        msg!("KOL {} PnL {} at epoch {}", kol_ticker, pnl, epoch);
+        emit!(KolPnlRecorded {
+            kol_record: performance.kol_record,
+            epoch,
+            pnl,
+            timestamp,
+        });
+        Ok(())
+    }
+}
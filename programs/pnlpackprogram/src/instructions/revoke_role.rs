@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, Role, RoleAssignment};
+
+// the assignment is closed once its last role is revoked
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct RevokeRole<'info> {
+  #[account(
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    mut,
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [b"role", global_pack_pool.key().as_ref(), member.as_ref()],
+    bump = role_assignment.bump
+  )]
+  pub role_assignment: Account<'info, RoleAssignment>,
+}
+
+impl<'info> RevokeRole<'info> {
+  pub fn handler(ctx: Context<RevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+    let assignment = &mut ctx.accounts.role_assignment;
+    assignment.roles &= !role.bit();
+    msg!("Revoked {:?} from {}", role, member);
+
+    if assignment.roles == 0 {
+      ctx.accounts.role_assignment.close(ctx.accounts.admin.to_account_info())?;
+    }
+    Ok(())
+  }
+}
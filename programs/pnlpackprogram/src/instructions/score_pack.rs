@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::events::PackScored;
+use crate::state::{GlobalPackPool, KolPnlReading, KolRecord, Leaderboard, LeaderboardEntry, Pack, PackScore, PackStatus};
+
+// accounts passed per pack slot, in slot order
+const ACCOUNTS_PER_SLOT: usize = 3;
+
+// permissionless; scores a funded pack from its KOLs' PnL change between the season's
+// start_epoch and end_epoch readings and updates the season leaderboard. Only packs whose
+// funding was complete before every start reading take part, so the allocations scored are
+// the ones held through the whole season and the readings never change once posted
+// remaining accounts: for every slot [KolRecord, KolPnlReading at start_epoch, KolPnlReading at end_epoch]
+#[derive(Accounts)]
+pub struct ScorePack<'info> {
+  #[account(
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+  #[account(
+    mut,
+    seeds = [b"leaderboard", global_pack_pool.key().as_ref(), leaderboard.season.to_le_bytes().as_ref()],
+    bump = leaderboard.bump
+  )]
+  pub leaderboard: Box<Account<'info, Leaderboard>>,
+
+  #[account(
+    mut,
+    seeds = [b"pack", pack.id.to_le_bytes().as_ref()],
+    bump = pack.bump,
+    constraint = matches!(pack.status, PackStatus::Funded | PackStatus::Claimed) @ ErrorCode::InvalidPackStatus
+  )]
+  pub pack: Box<Account<'info, Pack>>,
+}
+
+impl<'info> ScorePack<'info> {
+  pub fn handler(ctx: Context<'_, '_, 'info, 'info, ScorePack<'info>>) -> Result<()> {
+    let pack = &ctx.accounts.pack;
+    let leaderboard = &ctx.accounts.leaderboard;
+    require!(
+      ctx.remaining_accounts.len() == pack.slots.len() * ACCOUNTS_PER_SLOT,
+      ErrorCode::PackAccountsMismatch
+    );
+
+    let mut score: i128 = 0;
+    for (slot, accounts) in pack.slots.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_SLOT)) {
+      let [record_info, start_info, end_info] = accounts else {
+        return err!(ErrorCode::PackAccountsMismatch);
+      };
+      let kol_record = Account::<KolRecord>::try_from(record_info)?;
+      require!(kol_record.ticker == slot.ticker, ErrorCode::KolRecordMismatch);
+      let start = Account::<KolPnlReading>::try_from(start_info)?;
+      let end = Account::<KolPnlReading>::try_from(end_info)?;
+      require_keys_eq!(start.kol_record, kol_record.key(), ErrorCode::KolRecordMismatch);
+      require_keys_eq!(end.kol_record, kol_record.key(), ErrorCode::KolRecordMismatch);
+      require!(
+        start.epoch == leaderboard.start_epoch && end.epoch == leaderboard.end_epoch,
+        ErrorCode::PnlOutsideSeason
+      );
+      require!(pack.funded_at <= start.timestamp, ErrorCode::PackFundedAfterSeasonStart);
+
+      // weight by whole tokens so mints with different decimals compare
+      let unit = 10i128.checked_pow(kol_record.decimals as u32).ok_or(ErrorCode::MathOverflow)?;
+      let tokens = slot.allocated as i128 / unit;
+      let change = end.pnl as i128 - start.pnl as i128;
+      score = score.checked_add(change * tokens).ok_or(ErrorCode::MathOverflow)?;
+    }
+    let value = i64::try_from(score).map_err(|_| ErrorCode::MathOverflow)?;
+
+    let pack_key = pack.key();
+    let season = leaderboard.season;
+    ctx.accounts.pack.score = Some(PackScore {
+      season,
+      value,
+      scored_at: Clock::get()?.unix_timestamp,
+    });
+    ctx.accounts.leaderboard.submit(LeaderboardEntry {
+      pack: pack_key,
+      score: value,
+    });
+
+    msg!("Pack {} scored {} for season {}", pack_key, value, season);
+    emit!(PackScored {
+      pack: pack_key,
+      season,
+      score: value,
+    });
+    Ok(())
+  }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::constants::{BPS_DENOMINATOR, MAX_FEE_RECIPIENTS};
+use crate::errors::ErrorCode;
+use crate::state::{FeeRecipient, GlobalPackPool};
+
+#[derive(Accounts)]
+pub struct SetFeeRecipients<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> SetFeeRecipients<'info> {
+  pub fn handler(ctx: Context<SetFeeRecipients>, fee_recipients: Vec<FeeRecipient>) -> Result<()> {
+    require!(
+      !fee_recipients.is_empty() && fee_recipients.len() <= MAX_FEE_RECIPIENTS,
+      ErrorCode::InvalidFeeRecipients
+    );
+    let total_bps: u64 = fee_recipients.iter().map(|r| r.bps as u64).sum();
+    require!(total_bps == BPS_DENOMINATOR, ErrorCode::InvalidFeeRecipients);
+
+    ctx.accounts.global_pack_pool.fee_recipients = fee_recipients;
+    Ok(())
+  }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, KolStatus};
+
+// retired KOLs are dropped from the draw pool, re-activating puts them back
+#[derive(Accounts)]
+#[instruction(kol_ticker: String)]
+pub struct SetKolStatus<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump,
+    constraint = global_pack_pool.unrevealed_packs == 0 @ ErrorCode::RevealsPending
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+    bump = kol_record.bump
+  )]
+  pub kol_record: Account<'info, KolRecord>,
+}
+
+impl<'info> SetKolStatus<'info> {
+  pub fn handler(ctx: Context<SetKolStatus>, kol_ticker: String, status: KolStatus) -> Result<()> {
+    let pool = &mut ctx.accounts.global_pack_pool;
+    let listed = pool.kol_registry.iter().position(|ticker| *ticker == kol_ticker);
+
+    match (status, listed) {
+      (KolStatus::Retired, Some(position)) => {
+        pool.kol_registry.remove(position);
+      }
+      (KolStatus::Active, None) => {
+        require!(pool.kol_registry.len() < pool.total_kols as usize, ErrorCode::KolRegistryFull);
+        pool.kol_registry.push(kol_ticker.clone());
+      }
+      _ => {}
+    }
+
+    ctx.accounts.kol_record.status = status;
+    msg!("KOL {} status updated", kol_ticker);
+    Ok(())
+  }
+}
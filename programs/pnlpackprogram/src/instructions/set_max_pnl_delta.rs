@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::GlobalPackPool;
+
+#[derive(Accounts)]
+pub struct SetMaxPnlDelta<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> SetMaxPnlDelta<'info> {
+  pub fn handler(ctx: Context<SetMaxPnlDelta>, max_pnl_delta: u64) -> Result<()> {
+    ctx.accounts.global_pack_pool.max_pnl_delta = max_pnl_delta;
+    msg!("Max PnL delta set to {}", max_pnl_delta);
+    Ok(())
+  }
+}
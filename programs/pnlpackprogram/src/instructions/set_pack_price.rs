@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::GlobalPackPool;
+
+#[derive(Accounts)]
+pub struct SetPackPrice<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> SetPackPrice<'info> {
+  pub fn handler(ctx: Context<SetPackPrice>, pack_price: u64) -> Result<()> {
+    ctx.accounts.global_pack_pool.pack_price = pack_price;
+    msg!("Pack price set to {} lamports", pack_price);
+    Ok(())
+  }
+}
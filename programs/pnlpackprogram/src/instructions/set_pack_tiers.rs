@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_KOLS_PER_PACK, MAX_SLOT_OUTCOMES, MAX_TIERS};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, TierConfig};
+
+#[derive(Accounts)]
+pub struct SetPackTiers<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump,
+    constraint = global_pack_pool.unrevealed_packs == 0 @ ErrorCode::RevealsPending
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> SetPackTiers<'info> {
+  // an empty list switches tiers off, packs revealed afterwards are untiered
+  pub fn handler(ctx: Context<SetPackTiers>, tiers: Vec<TierConfig>) -> Result<()> {
+    require!(tiers.len() <= MAX_TIERS, ErrorCode::InvalidTierConfig);
+    for (i, tier) in tiers.iter().enumerate() {
+      require!(tier.weight > 0, ErrorCode::InvalidTierConfig);
+      require!(tiers[..i].iter().all(|other| other.tier != tier.tier), ErrorCode::InvalidTierConfig);
+      // one table per possible slot, so any pack size can be revealed under any tier
+      require!(tier.slot_tables.len() == MAX_KOLS_PER_PACK as usize, ErrorCode::InvalidTierConfig);
+      require!(tier.vesting.is_none_or(|vesting| vesting.is_valid()), ErrorCode::InvalidVestingConfig);
+      for table in tier.slot_tables.iter() {
+        require!(
+          !table.outcomes.is_empty() && table.outcomes.len() <= MAX_SLOT_OUTCOMES,
+          ErrorCode::InvalidTierConfig
+        );
+        require!(table.outcomes.iter().any(|outcome| outcome.weight > 0), ErrorCode::InvalidTierConfig);
+      }
+    }
+
+    ctx.accounts.global_pack_pool.tiers = tiers;
+    Ok(())
+  }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, PauseFlags, Role, RoleAssignment};
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Pauser) @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+
+  #[account(
+    seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+    bump = role.bump
+  )]
+  pub role: Option<Account<'info, RoleAssignment>>,
+}
+
+impl<'info> SetPauseFlags<'info> {
+  // replaces all flags at once, pass the current flags with one changed to toggle a single group
+  pub fn handler(ctx: Context<SetPauseFlags>, paused: PauseFlags) -> Result<()> {
+    let current = ctx.accounts.global_pack_pool.paused;
+    let lifts = (current.raise && !paused.raise)
+      || (current.reveal && !paused.reveal)
+      || (current.funding && !paused.funding)
+      || (current.claims && !paused.claims);
+    // pausers can only pause
+    require!(
+      !lifts || ctx.accounts.admin.key() == ctx.accounts.global_pack_pool.authority,
+      ErrorCode::Unauthorized
+    );
+
+    ctx.accounts.global_pack_pool.paused = paused;
+    msg!(
+      "Pause flags: raise {} reveal {} funding {} claims {}",
+      paused.raise,
+      paused.reveal,
+      paused.funding,
+      paused.claims
+    );
+    Ok(())
+  }
+}
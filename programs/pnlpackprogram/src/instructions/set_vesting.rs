@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, VestingConfig};
+
+#[derive(Accounts)]
+pub struct SetVesting<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> SetVesting<'info> {
+  // None pays claims out immediately; only packs revealed afterwards pick up the change
+  pub fn handler(ctx: Context<SetVesting>, vesting: Option<VestingConfig>) -> Result<()> {
+    require!(vesting.is_none_or(|vesting| vesting.is_valid()), ErrorCode::InvalidVestingConfig);
+    ctx.accounts.global_pack_pool.vesting = vesting;
+    Ok(())
+  }
+}
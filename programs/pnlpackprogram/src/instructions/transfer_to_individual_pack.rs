@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked};
-use crate::state::GlobalPackPool;
-use super::pack_reveal::Pack;
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, KolRecord, Pack, PackStatus, Role, RoleAssignment};
 
 #[derive(Accounts)]
 #[instruction(kol: String)]
@@ -10,23 +10,40 @@ pub struct TransferToIndividualPack<'info> {
     #[account(
         mut,
         seeds = [b"global_pack_pool"],
-        bump
+        bump,
+        constraint = !global_pack_pool.paused.funding @ ErrorCode::ProgramPaused
     )]
     pub global_pack_pool: Account<'info, GlobalPackPool>,
 
     #[account(
         mut,
-        seeds = [
-            b"pack",
-            pack_account.kol_a.as_bytes(),
-            pack_account.kol_b.as_bytes(),
-            pack_account.kol_c.as_bytes(),
-            pack_account.kol_d.as_bytes(),
-        ],
-        bump = pack_account.bump
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Operator) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"pack", pack_account.id.to_le_bytes().as_ref()],
+        bump = pack_account.bump,
+        constraint = matches!(pack_account.status, PackStatus::Revealed | PackStatus::Funded | PackStatus::Claimed) @ ErrorCode::PackNotRevealed
     )]
     pub pack_account: Account<'info, Pack>,
 
+    #[account(
+        mut,
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol.as_bytes()],
+        bump = kol_record.bump,
+        constraint = kol_record.mint == kol_mint.key() @ ErrorCode::KolMintMismatch,
+        constraint = kol_record.vault == kol_token_vault.key() @ ErrorCode::KolMintMismatch
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
     #[account(
         mut,
         mint::token_program = token_program,
@@ -44,13 +61,15 @@ pub struct TransferToIndividualPack<'info> {
     pub kol_token_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
-        mut,
+        init_if_needed,
+        payer = admin,
         associated_token::mint = kol_mint,
         associated_token::authority = pack_account,
         associated_token::token_program = token_program,
     )]
     pub pack_kol_ta: InterfaceAccount<'info, TokenAccount>,
 
+    pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
@@ -62,6 +81,11 @@ impl<'info> TransferToIndividualPack<'info> {
         amount: u64,
     ) -> Result<()> {
         msg!("🔵 [TransferToIndividualPack] Transferring {} tokens for {}", amount, kol);
+        ctx.accounts.pack_account.record_funding(&kol, amount, Clock::get()?.unix_timestamp)?;
+        // tiered slots were reserved at reveal, untiered funding is committed here
+        if ctx.accounts.pack_account.tier.is_none() {
+            ctx.accounts.kol_record.allocate(amount)?;
+        }
 
         let bump = ctx.bumps.global_pack_pool;
         let signer_seeds: &[&[&[u8]]] = &[&[
@@ -81,6 +105,7 @@ impl<'info> TransferToIndividualPack<'info> {
             signer_seeds,
         );
         transfer_checked(cpi_ctx, amount, ctx.accounts.kol_mint.decimals)?;
+
         msg!("✅ [TransferToIndividualPack] Transferred {} tokens to pack ATA", amount);
         Ok(())
     }
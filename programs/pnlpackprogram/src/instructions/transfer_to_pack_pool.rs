@@ -1,16 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use crate::state::GlobalPackPool;
+use crate::errors::ErrorCode;
+use crate::state::{Contribution, GlobalPackPool};
 
 #[derive(Accounts)]
 pub struct TransferToPackPool<'info> {
     #[account(
         mut,
         seeds = [b"global_pack_pool"],
-        bump
+        bump,
+        constraint = !global_pack_pool.paused.raise @ ErrorCode::ProgramPaused
     )]
     pub global_pack_pool: Account<'info, GlobalPackPool>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Contribution::INIT_SPACE,
+        seeds = [b"contribution", global_pack_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -19,6 +30,18 @@ pub struct TransferToPackPool<'info> {
 
 impl<'info> TransferToPackPool<'info> {
     pub fn handler(ctx: Context<TransferToPackPool>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let raise = ctx.accounts.global_pack_pool.raise;
+        require!(now >= raise.start_ts, ErrorCode::RaiseNotStarted);
+        require!(now < raise.end_ts, ErrorCode::RaiseEnded);
+
+        let pool_total = ctx.accounts.global_pack_pool.total_raised.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(pool_total <= raise.hard_cap, ErrorCode::HardCapExceeded);
+
+        let wallet_total = ctx.accounts.contribution.lamports.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(wallet_total >= raise.min_per_wallet, ErrorCode::ContributionBelowMinimum);
+        require!(wallet_total <= raise.max_per_wallet, ErrorCode::ContributionAboveMaximum);
+
         // sol transfer to pack pool during the raise period
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
@@ -30,6 +53,32 @@ impl<'info> TransferToPackPool<'info> {
 
         system_program::transfer(cpi_context, amount)?;
 
+        let pool = &mut ctx.accounts.global_pack_pool;
+        let contribution = &mut ctx.accounts.contribution;
+
+        // first contribution from this user, the ledger was just created
+        if contribution.contribution_count == 0 {
+            contribution.bump = ctx.bumps.contribution;
+            contribution.pool = pool.key();
+            contribution.user = ctx.accounts.user.key();
+            contribution.first_contribution_ts = now;
+            pool.total_contributors = pool.total_contributors.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        contribution.lamports = wallet_total;
+        contribution.contribution_count = contribution.contribution_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        contribution.last_contribution_ts = now;
+
+        pool.total_raised = pool_total;
+
+        msg!(
+            "Contribution of {} lamports from {} (total {}, pool raised {})",
+            amount,
+            contribution.user,
+            contribution.lamports,
+            pool.total_raised,
+        );
+
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::instructions::UpdateMetadataAccountV2CpiBuilder;
+use crate::errors::ErrorCode;
+use crate::instructions::create_kol_metadata::kol_metadata;
+use crate::state::{GlobalPackPool, KolRecord, Role, RoleAssignment};
+
+// re-points the KOL metadata at a new URI, signed by the pool PDA as update authority
+#[derive(Accounts)]
+#[instruction(kol_ticker: String)]
+pub struct UpdateKolMetadata<'info> {
+    #[account(
+        seeds = [b"global_pack_pool"],
+        bump = global_pack_pool.bump
+    )]
+    pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+    #[account(
+        constraint = global_pack_pool.has_role(&admin.key(), role.as_deref(), Role::Minter) @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"role", global_pack_pool.key().as_ref(), admin.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"kol", global_pack_pool.key().as_ref(), kol_ticker.as_bytes()],
+        bump = kol_record.bump
+    )]
+    pub kol_record: Box<Account<'info, KolRecord>>,
+
+    /// CHECK: derived from the KOL mint
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), kol_record.mint.as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: address is checked
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+impl<'info> UpdateKolMetadata<'info> {
+    pub fn handler(ctx: Context<UpdateKolMetadata>, kol_ticker: String, symbol: String, uri: String) -> Result<()> {
+        let data = kol_metadata(&ctx.accounts.kol_record, symbol, uri)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global_pack_pool", &[ctx.accounts.global_pack_pool.bump]]];
+        let pool_info = ctx.accounts.global_pack_pool.to_account_info();
+
+        UpdateMetadataAccountV2CpiBuilder::new(&ctx.accounts.token_metadata_program)
+            .metadata(&ctx.accounts.metadata)
+            .update_authority(&pool_info)
+            .data(data)
+            .invoke_signed(signer_seeds)?;
+
+        msg!("Metadata updated for KOL {}", kol_ticker);
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::ErrorCode;
+use crate::events::TreasuryPayout;
+use crate::state::GlobalPackPool;
+
+// recipients are passed as writable remaining accounts, in the same order as
+// global_pack_pool.fee_recipients
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+  #[account(
+    mut,
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump
+  )]
+  pub global_pack_pool: Account<'info, GlobalPackPool>,
+
+  #[account(
+    address = global_pack_pool.authority @ ErrorCode::Unauthorized
+  )]
+  pub admin: Signer<'info>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+  pub fn handler(ctx: Context<'_, '_, 'info, 'info, WithdrawTreasury<'info>>, amount: u64) -> Result<()> {
+    let pool = &ctx.accounts.global_pack_pool;
+
+    // contributions stay locked while they could still be refunded, pack sale revenue never is
+    let refundable = if pool.total_raised >= pool.raise.soft_cap {
+      0
+    } else {
+      pool.total_raised.saturating_sub(pool.total_refunded)
+    };
+    require!(!pool.fee_recipients.is_empty(), ErrorCode::InvalidFeeRecipients);
+    require!(
+      ctx.remaining_accounts.len() == pool.fee_recipients.len(),
+      ErrorCode::FeeRecipientMismatch
+    );
+
+    let pool_info = pool.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(pool_info.data_len());
+    let available = pool_info.lamports().saturating_sub(rent_exempt).saturating_sub(refundable);
+    require!(amount <= available, ErrorCode::InsufficientTreasury);
+
+    let fee_recipients = pool.fee_recipients.clone();
+    for (fee_recipient, recipient_info) in fee_recipients.iter().zip(ctx.remaining_accounts.iter()) {
+      require_keys_eq!(recipient_info.key(), fee_recipient.recipient, ErrorCode::FeeRecipientMismatch);
+
+      // rounding dust stays in the pool
+      let payout = (amount as u128 * fee_recipient.bps as u128 / BPS_DENOMINATOR as u128) as u64;
+      if payout == 0 {
+        continue;
+      }
+
+      ctx.accounts.global_pack_pool.sub_lamports(payout)?;
+      recipient_info.add_lamports(payout)?;
+
+      emit!(TreasuryPayout {
+        recipient: fee_recipient.recipient,
+        kind: fee_recipient.kind,
+        bps: fee_recipient.bps,
+        amount: payout,
+      });
+    }
+
+    msg!("Withdrew {} lamports from treasury", amount);
+    Ok(())
+  }
+}
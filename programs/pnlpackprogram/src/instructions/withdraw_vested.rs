@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface, transfer_checked, TransferChecked};
+use crate::errors::ErrorCode;
+use crate::state::{GlobalPackPool, VestingEscrow};
+
+// releases whatever has unlocked from a vesting escrow, the escrow and its ATA are
+// closed back to the user once everything is withdrawn
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+  #[account(
+    seeds = [b"global_pack_pool"],
+    bump = global_pack_pool.bump,
+    constraint = !global_pack_pool.paused.claims @ ErrorCode::ProgramPaused
+  )]
+  pub global_pack_pool: Box<Account<'info, GlobalPackPool>>,
+
+  #[account(
+    mut,
+    seeds = [b"vesting", escrow.pack.as_ref(), escrow.mint.as_ref()],
+    bump = escrow.bump,
+    has_one = user @ ErrorCode::Unauthorized
+  )]
+  pub escrow: Box<Account<'info, VestingEscrow>>,
+
+  #[account(
+    mut,
+    associated_token::mint = mint,
+    associated_token::authority = escrow,
+    associated_token::token_program = token_program,
+  )]
+  pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  #[account(
+    address = escrow.mint @ ErrorCode::KolMintMismatch,
+    mint::token_program = token_program,
+  )]
+  pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = mint,
+    associated_token::authority = user,
+    associated_token::token_program = token_program,
+  )]
+  pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub system_program: Program<'info, System>,
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> WithdrawVested<'info> {
+  pub fn handler(ctx: Context<WithdrawVested>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let unlocked = escrow.schedule.unlocked(escrow.total, escrow.start_ts, Clock::get()?.unix_timestamp);
+    let amount = unlocked.checked_sub(escrow.withdrawn).ok_or(ErrorCode::MathOverflow)?;
+    require!(amount > 0, ErrorCode::NothingVested);
+
+    let (pack, mint, bump) = (escrow.pack, escrow.mint, escrow.bump);
+    let signer_seeds: &[&[&[u8]]] = &[&[
+      b"vesting",
+      pack.as_ref(),
+      mint.as_ref(),
+      &[bump],
+    ]];
+    // the final tranche sweeps the whole escrow ATA, tokens sent to it directly would
+    // otherwise make close_account fail and lock the last withdrawal
+    let payout = if unlocked == escrow.total {
+      ctx.accounts.escrow_token_account.amount
+    } else {
+      amount
+    };
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+
+    let accounts = TransferChecked {
+      from: ctx.accounts.escrow_token_account.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      to: ctx.accounts.user_token_account.to_account_info(),
+      authority: escrow_info.clone(),
+    };
+    transfer_checked(
+      CpiContext::new_with_signer(token_program.clone(), accounts, signer_seeds),
+      payout,
+      ctx.accounts.mint.decimals,
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.withdrawn = unlocked;
+    msg!("Withdrew {} of {} vested tokens for {}", escrow.withdrawn, escrow.total, escrow.user);
+
+    if escrow.withdrawn == escrow.total {
+      let accounts = CloseAccount {
+        account: ctx.accounts.escrow_token_account.to_account_info(),
+        destination: ctx.accounts.user.to_account_info(),
+        authority: escrow_info,
+      };
+      close_account(CpiContext::new_with_signer(token_program, accounts, signer_seeds))?;
+      ctx.accounts.escrow.close(ctx.accounts.user.to_account_info())?;
+    }
+    Ok(())
+  }
+}
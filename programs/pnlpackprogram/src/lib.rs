@@ -1,10 +1,17 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
 mod instructions;
 mod state;
 mod constants;
 mod errors;
+mod events;
+mod randomness;
+mod merkle;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::*;
 
 declare_id!("Cn3xRT72q5c99rMZKseUF8TkTFrpWTFBqMoLs3pNu2ZX");
 
@@ -17,8 +24,8 @@ pub mod pnlpackprogram {
         Ok(())
     }
 
-    pub fn init_global_pack_pool(ctx: Context<InitGlobalPackPool>, total_kols: u8) -> Result<()> {
-        InitGlobalPackPool::handler(ctx, total_kols)
+    pub fn init_global_pack_pool(ctx: Context<InitGlobalPackPool>, total_kols: u8, raise: RaiseConfig) -> Result<()> {
+        InitGlobalPackPool::handler(ctx, total_kols, raise)
     }
 
     pub fn transfer_to_pack_pool(ctx: Context<TransferToPackPool>, amount: u64) -> Result<()> {
@@ -39,20 +46,158 @@ pub mod pnlpackprogram {
     // }
 
     // Super combined instruction: mint 1B tokens to admin + init vault + 940M tokens admin->vault transfer
-    pub fn mint_and_init_kol_token_vault_and_transfer(ctx: Context<MintAndInitKolTokenVaultAndTransfer>, kol_ticker: String, total_supply: u64, vault_transfer_amount: u64) -> Result<()> {
-        MintAndInitKolTokenVaultAndTransfer::handler(ctx, kol_ticker, total_supply, vault_transfer_amount)
+    pub fn mint_and_init_kol_token_vault_and_transfer(ctx: Context<MintAndInitKolTokenVaultAndTransfer>, kol_ticker: String, display_name: String, total_supply: u64, vault_transfer_amount: u64) -> Result<()> {
+        MintAndInitKolTokenVaultAndTransfer::handler(ctx, kol_ticker, display_name, total_supply, vault_transfer_amount)
+    }
+
+    pub fn commit_pack(ctx: Context<CommitPack>, owner: Pubkey, size: u8) -> Result<()> {
+        CommitPack::handler(ctx, owner, size)
     }
 
-    pub fn pack_reveal(ctx: Context<PackReveal>, kol_a: String, kol_b: String, kol_c: String, kol_d: String) -> Result<()> {
-        PackReveal::handler(ctx, kol_a, kol_b, kol_c, kol_d)
+    pub fn pack_reveal<'info>(ctx: Context<'_, '_, 'info, 'info, PackReveal<'info>>) -> Result<()> {
+        PackReveal::handler(ctx)
     }
 
     pub fn transfer_to_individual_pack(ctx: Context<TransferToIndividualPack>, kol: String, amount: u64) -> Result<()> {
         TransferToIndividualPack::handler(ctx, kol, amount)
     }
 
-    pub fn claim_from_pack(ctx: Context<ClaimFromPack>, kol_a: String, kol_b: String, kol_c: String, kol_d: String, amount_per_kol: u64) -> Result<()> {
-        ClaimFromPack::handler(ctx, kol_a, kol_b, kol_c, kol_d, amount_per_kol)
+    pub fn claim_from_pack<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimFromPack<'info>>) -> Result<()> {
+        ClaimFromPack::handler(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        ProposeAuthority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        AcceptAuthority::handler(ctx)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        ClaimRefund::handler(ctx)
+    }
+
+    pub fn set_fee_recipients(ctx: Context<SetFeeRecipients>, fee_recipients: Vec<FeeRecipient>) -> Result<()> {
+        SetFeeRecipients::handler(ctx, fee_recipients)
+    }
+
+    pub fn withdraw_treasury<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawTreasury<'info>>, amount: u64) -> Result<()> {
+        WithdrawTreasury::handler(ctx, amount)
+    }
+
+    pub fn set_pack_price(ctx: Context<SetPackPrice>, pack_price: u64) -> Result<()> {
+        SetPackPrice::handler(ctx, pack_price)
+    }
+
+    pub fn buy_pack(ctx: Context<BuyPack>) -> Result<()> {
+        BuyPack::handler(ctx)
+    }
+
+    pub fn set_kol_status(ctx: Context<SetKolStatus>, kol_ticker: String, status: KolStatus) -> Result<()> {
+        SetKolStatus::handler(ctx, kol_ticker, status)
+    }
+
+    pub fn set_pack_tiers(ctx: Context<SetPackTiers>, tiers: Vec<TierConfig>) -> Result<()> {
+        SetPackTiers::handler(ctx, tiers)
+    }
+
+    pub fn close_pack<'info>(ctx: Context<'_, '_, 'info, 'info, ClosePack<'info>>) -> Result<()> {
+        ClosePack::handler(ctx)
+    }
+
+    pub fn init_pack_collection(ctx: Context<InitPackCollection>, name: String, symbol: String, uri: String) -> Result<()> {
+        InitPackCollection::handler(ctx, name, symbol, uri)
+    }
+
+    pub fn create_kol_metadata(ctx: Context<CreateKolMetadata>, kol_ticker: String, symbol: String, uri: String) -> Result<()> {
+        CreateKolMetadata::handler(ctx, kol_ticker, symbol, uri)
+    }
+
+    pub fn update_kol_metadata(ctx: Context<UpdateKolMetadata>, kol_ticker: String, symbol: String, uri: String) -> Result<()> {
+        UpdateKolMetadata::handler(ctx, kol_ticker, symbol, uri)
+    }
+
+    pub fn lock_kol_supply(ctx: Context<LockKolSupply>, kol_ticker: String, revoke: bool) -> Result<()> {
+        LockKolSupply::handler(ctx, kol_ticker, revoke)
+    }
+
+    pub fn batch_transfer_to_packs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransferToPacks<'info>>,
+        entries: Vec<PackFunding>,
+    ) -> Result<()> {
+        BatchTransferToPacks::handler(ctx, entries)
+    }
+
+    pub fn create_distributor<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateDistributor<'info>>,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        totals: Vec<u64>,
+    ) -> Result<()> {
+        CreateDistributor::handler(ctx, merkle_root, num_leaves, totals)
+    }
+
+    pub fn claim_from_distributor(
+        ctx: Context<ClaimFromDistributor>,
+        kol: String,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ClaimFromDistributor::handler(ctx, kol, index, amount, proof)
+    }
+
+    pub fn set_vesting(ctx: Context<SetVesting>, vesting: Option<VestingConfig>) -> Result<()> {
+        SetVesting::handler(ctx, vesting)
+    }
+
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        WithdrawVested::handler(ctx)
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, paused: PauseFlags) -> Result<()> {
+        SetPauseFlags::handler(ctx, paused)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, member: Pubkey, role: Role) -> Result<()> {
+        GrantRole::handler(ctx, member, role)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+        RevokeRole::handler(ctx, member, role)
+    }
+
+    pub fn set_max_pnl_delta(ctx: Context<SetMaxPnlDelta>, max_pnl_delta: u64) -> Result<()> {
+        SetMaxPnlDelta::handler(ctx, max_pnl_delta)
+    }
+
+    pub fn record_kol_pnl(ctx: Context<RecordKolPnl>, kol_ticker: String, epoch: u64, pnl: i64, timestamp: i64) -> Result<()> {
+        RecordKolPnl::handler(ctx, kol_ticker, epoch, pnl, timestamp)
+    }
+
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>, season: u32, start_epoch: u64, end_epoch: u64) -> Result<()> {
+        InitLeaderboard::handler(ctx, season, start_epoch, end_epoch)
+    }
+
+    pub fn score_pack<'info>(ctx: Context<'_, '_, 'info, 'info, ScorePack<'info>>) -> Result<()> {
+        ScorePack::handler(ctx)
+    }
+
+    pub fn deposit_to_kol_vault(ctx: Context<DepositToKolVault>, kol_ticker: String, amount: u64) -> Result<()> {
+        DepositToKolVault::handler(ctx, kol_ticker, amount)
+    }
+
+    pub fn cancel_pack<'info>(ctx: Context<'_, '_, 'info, 'info, CancelPack<'info>>) -> Result<()> {
+        CancelPack::handler(ctx)
+    }
+
+    pub fn recommit_pack(ctx: Context<RecommitPack>) -> Result<()> {
+        RecommitPack::handler(ctx)
+    }
+
+    pub fn close_distributor<'info>(ctx: Context<'_, '_, 'info, 'info, CloseDistributor<'info>>) -> Result<()> {
+        CloseDistributor::handler(ctx)
     }
 }
 
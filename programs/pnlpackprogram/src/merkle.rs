@@ -0,0 +1,23 @@
+// merkle proofs for distributor claims
+//
+// leaves are hash(0x00, index, user, kol mint, amount) and inner nodes are
+// hash(0x01, a, b) with the pair sorted, so proofs don't need left/right flags
+// and a leaf can never be passed off as an inner node
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(index: u32, user: &Pubkey, mint: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &index.to_le_bytes(), user.as_ref(), mint.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (a, b) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[NODE_PREFIX, &a, &b]).to_bytes()
+    });
+    computed == *root
+}
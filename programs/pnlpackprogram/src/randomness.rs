@@ -0,0 +1,71 @@
+// slot-hash based randomness for pack reveals
+//
+// a pack is committed to a future slot; once that slot has passed its hash is
+// read from the SlotHashes sysvar and mixed with the pack address, so neither
+// the admin nor the buyer can know the outcome when the pack is committed
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::errors::ErrorCode;
+
+// SlotHashes layout: u64 entry count, then (u64 slot, [u8; 32] hash) entries, newest first
+const SLOT_HASHES_LEN_PREFIX: usize = 8;
+const SLOT_HASH_ENTRY_SIZE: usize = 8 + 32;
+
+// returns None once `slot` has rotated out of the sysvar (~512 slots)
+pub fn slot_hash_at(slot_hashes: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>> {
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= SLOT_HASHES_LEN_PREFIX, ErrorCode::InvalidSlotHashes);
+
+    let count = u64::from_le_bytes(data[..SLOT_HASHES_LEN_PREFIX].try_into().unwrap()) as usize;
+    for i in 0..count {
+        let start = SLOT_HASHES_LEN_PREFIX + i * SLOT_HASH_ENTRY_SIZE;
+        let Some(entry) = data.get(start..start + SLOT_HASH_ENTRY_SIZE) else {
+            break;
+        };
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if entry_slot == slot {
+            return Ok(Some(entry[8..].try_into().unwrap()));
+        }
+        // entries are sorted newest first
+        if entry_slot < slot {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+pub fn derive_seed(slot_hash: &[u8; 32], pack: &Pubkey, pack_id: u64) -> [u8; 32] {
+    hashv(&[slot_hash, pack.as_ref(), &pack_id.to_le_bytes()]).to_bytes()
+}
+
+// partial Fisher-Yates over 0..n, yields `k` distinct indexes
+pub fn draw_distinct(seed: &[u8; 32], n: usize, k: usize) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..n).collect();
+    for i in 0..k.min(n) {
+        let roll = hashv(&[seed, &(i as u64).to_le_bytes()]).to_bytes();
+        let r = u64::from_le_bytes(roll[..8].try_into().unwrap()) as usize;
+        let j = i + r % (n - i);
+        indexes.swap(i, j);
+    }
+    indexes.truncate(k);
+    indexes
+}
+
+// picks an index with probability proportional to its weight, `domain` keeps
+// independent rolls from the same seed uncorrelated
+pub fn roll_weighted(seed: &[u8; 32], domain: &[u8], weights: &[u16]) -> usize {
+    let total: u64 = weights.iter().map(|w| *w as u64).sum();
+    if total == 0 {
+        return 0;
+    }
+    let roll = hashv(&[seed, domain]).to_bytes();
+    let mut r = u64::from_le_bytes(roll[..8].try_into().unwrap()) % total;
+    for (i, weight) in weights.iter().enumerate() {
+        if r < *weight as u64 {
+            return i;
+        }
+        r -= *weight as u64;
+    }
+    weights.len() - 1
+}
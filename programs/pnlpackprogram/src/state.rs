@@ -1,24 +1,519 @@
 // account structs
 
 use anchor_lang::prelude::*;
+use crate::constants::LEADERBOARD_SIZE;
+use crate::errors::ErrorCode;
 
 #[account]
 #[derive(InitSpace)]
 pub struct GlobalPackPool {
     pub bump: u8,
     pub total_kols: u8,
+    // key allowed to run every privileged instruction
+    pub authority: Pubkey,
+    // set by propose_authority, becomes `authority` once it signs accept_authority
+    pub pending_authority: Option<Pubkey>,
+    // running totals of transfer_to_pack_pool
+    pub total_raised: u64,
+    pub total_contributors: u32,
+    pub total_refunded: u64,
+    pub raise: RaiseConfig,
+    // withdraw_treasury splits every withdrawal across these, bps must sum to 10_000
+    #[max_len(5)]
+    pub fee_recipients: Vec<FeeRecipient>,
+    // lamports charged by buy_pack, sales are closed while this is 0
+    pub pack_price: u64,
+    pub packs_sold: u32,
+    // next pack id
+    pub pack_count: u64,
+    // tickers registered by mint_and_init_kol_token_vault_and_transfer, pack_reveal draws from these
+    #[max_len(64, 16)]
+    pub kol_registry: Vec<String>,
+    // total KolRecords ever created, used as the next KolRecord::index
+    pub kols_registered: u16,
+    // rarity tiers rolled by pack_reveal, packs are untiered while this is empty
+    #[max_len(3)]
+    pub tiers: Vec<TierConfig>,
+    // set by init_pack_collection, buy_pack mints every sold pack into this collection
+    pub pack_collection: Option<PackCollection>,
+    pub distributor_count: u64,
+    // default vesting for claimed tokens, a tier's own schedule takes precedence
+    pub vesting: Option<VestingConfig>,
+    pub paused: PauseFlags,
+    // largest change between two consecutive PnL readings of a KOL, 0 leaves readings unbounded
+    pub max_pnl_delta: u64,
+    // lamports collected by buy_pack, never refundable so withdraw_treasury can always pay them out
+    pub sales_revenue: u64,
+    // packs with a reveal slot committed (assigned or bought) that are not yet revealed or cancelled,
+    // the registry and tiers are frozen while non-zero
+    pub unrevealed_packs: u32,
 }
 
+// emergency switches toggled by set_pause_flags, each one halts a group of instructions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PauseFlags {
+    // transfer_to_pack_pool, buy_pack
+    pub raise: bool,
+    // commit_pack, pack_reveal
+    pub reveal: bool,
+    // transfer_to_individual_pack, batch_transfer_to_packs
+    pub funding: bool,
+    // claim_from_pack, claim_from_distributor, withdraw_vested, claim_refund
+    pub claims: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PackCollection {
+    pub mint: Pubkey,
+    // pack NFTs are named "<name> #<pack id>"
+    #[max_len(20)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    #[max_len(200)]
+    pub uri: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum PackTier {
+    Common,
+    Rare,
+    Legendary,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TierConfig {
+    pub tier: PackTier,
+    // chance of rolling this tier relative to the other tiers
+    pub weight: u16,
+    // one table per slot position, each slot rolls its token amount from its table
+    #[max_len(8)]
+    pub slot_tables: Vec<SlotTable>,
+    pub vesting: Option<VestingConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SlotTable {
+    #[max_len(4)]
+    pub outcomes: Vec<WeightedAmount>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct WeightedAmount {
+    pub weight: u16,
+    // base units of whichever KOL mint lands in the slot
+    pub amount: u64,
+}
+
+// claimed tokens unlock linearly every `period_seconds` over `duration_seconds`,
+// nothing unlocks before the cliff
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct VestingConfig {
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+    pub period_seconds: i64,
+}
+
+impl VestingConfig {
+    pub fn is_valid(&self) -> bool {
+        self.duration_seconds > 0
+            && self.period_seconds > 0
+            && self.period_seconds <= self.duration_seconds
+            && (0..=self.duration_seconds).contains(&self.cliff_seconds)
+    }
+
+    pub fn unlocked(&self, total: u64, start_ts: i64, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(start_ts);
+        if elapsed < self.cliff_seconds {
+            return 0;
+        }
+        if elapsed >= self.duration_seconds {
+            return total;
+        }
+        let vested_time = elapsed / self.period_seconds * self.period_seconds;
+        (total as u128 * vested_time as u128 / self.duration_seconds as u128) as u64
+    }
+}
+
+// raise window and caps enforced by transfer_to_pack_pool, all amounts in lamports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct RaiseConfig {
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub min_per_wallet: u64,
+    pub max_per_wallet: u64,
+}
+
+impl RaiseConfig {
+    pub fn is_valid(&self) -> bool {
+        self.start_ts < self.end_ts
+            && self.soft_cap <= self.hard_cap
+            && self.hard_cap > 0
+            && self.min_per_wallet <= self.max_per_wallet
+            && self.max_per_wallet > 0
+    }
+
+    // the raise window closed without reaching the soft cap, contributors may claim refunds
+    pub fn has_failed(&self, now: i64, total_raised: u64) -> bool {
+        now >= self.end_ts && total_raised < self.soft_cap
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum FeeRecipientKind {
+    Team,
+    KolRevenueShare,
+    LiquidityReserve,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct FeeRecipient {
+    pub recipient: Pubkey,
+    pub kind: FeeRecipientKind,
+    pub bps: u16,
+}
+
+// per-user ledger of SOL sent to the pool, seeds = [b"contribution", pool, user]
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub bump: u8,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub lamports: u64,
+    pub contribution_count: u32,
+    pub first_contribution_ts: i64,
+    pub last_contribution_ts: i64,
+    pub refunded: bool,
+}
+
+// seeds = [b"pack", id.to_le_bytes()]; the id comes from GlobalPackPool::pack_count, so
+// addresses never depend on the KOLs inside and packs with identical contents can coexist
 #[account]
 #[derive(InitSpace)]
 pub struct Pack {
+    pub id: u64,
     pub bump: u8,
+    // only this key may claim the pack, Pubkey::default() while unassigned
+    pub owner: Pubkey,
+    // paid the rent for the pack and its token accounts, refunded by close_pack
+    pub payer: Pubkey,
+    // 1-of-1 NFT minted by buy_pack; when set, whoever holds it owns the pack. Cleared when the claim burns it
+    pub nft_mint: Option<Pubkey>,
+    // the hash of this slot decides which KOLs land in the pack
+    pub reveal_slot: u64,
+    // hash(slot hash, pack, id), recorded by pack_reveal so the draw can be re-derived
+    pub randomness_seed: [u8; 32],
+    pub status: PackStatus,
+    // unix timestamp of the latest claim, 0 until claimed
+    pub claimed_at: i64,
+    // unix timestamp of the latest funding, 0 until funded
+    pub funded_at: i64,
+    // number of KOLs drawn at reveal, fixed at commit
+    pub size: u8,
+    // rolled at reveal, None when the pool had no tiers configured
+    pub tier: Option<PackTier>,
+    // schedule in force when the pack was revealed, claims go to vesting escrows when set
+    pub vesting: Option<VestingConfig>,
+    // latest score_pack result
+    pub score: Option<PackScore>,
+    #[max_len(8)]
+    pub slots: Vec<PackSlot>,
+    // kol_registry length when the reveal slot was committed, the draw only picks from entries that existed by then
+    pub registry_len: u16,
+    // times recommit_pack moved reveal_slot because the committed slot hash had expired
+    pub recommits: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PackSlot {
     #[max_len(16)]
-    pub kol_a: String,
-    #[max_len(16)]
-    pub kol_b: String,
-    #[max_len(16)]
-    pub kol_c: String,
+    pub ticker: String,
+    // copied from the KolRecord at reveal
+    pub mint: Pubkey,
+    // amount rolled from the tier's slot table, funding may not exceed it on tiered packs
+    pub target: u64,
+    // tokens funded by transfer_to_individual_pack
+    pub allocated: u64,
+    // tokens paid out by claim_from_pack, never above allocated
+    pub claimed: u64,
+}
+
+// lifecycle order: Created -> Revealed -> Funded -> Claimed, then close_pack deletes the account.
+// untiered packs go back to Funded when topped up after a claim and can be claimed again
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum PackStatus {
+    // committed by commit_pack, KOLs not drawn yet
+    Created,
+    Revealed,
+    // at least one slot funded by transfer_to_individual_pack
+    Funded,
+    Claimed,
+}
+
+impl Pack {
+    pub fn slot_of(&self, kol: &str) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.ticker == kol)
+    }
+
+    // books tokens moved into a slot, tiered slots can't be funded past their rolled target
+    pub fn record_funding(&mut self, kol: &str, amount: u64, now: i64) -> Result<()> {
+        let slot = self.slot_of(kol).ok_or(ErrorCode::KolNotInPack)?;
+        let allocated = self.slots[slot].allocated.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(self.tier.is_none() || allocated <= self.slots[slot].target, ErrorCode::SlotTargetExceeded);
+        self.slots[slot].allocated = allocated;
+        self.status = PackStatus::Funded;
+        self.funded_at = now;
+        Ok(())
+    }
+
+    // untiered packs are claimable as soon as anything is funded
+    pub fn is_fully_funded(&self) -> bool {
+        self.tier.is_none() || self.slots.iter().all(|slot| slot.allocated == slot.target)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum KolStatus {
+    Active,
+    // kept for existing packs, but no longer drawn by pack_reveal
+    Retired,
+}
+
+// registry entry per KOL, seeds = [b"kol", pool, ticker]
+#[account]
+#[derive(InitSpace)]
+pub struct KolRecord {
+    pub bump: u8,
+    pub index: u16,
     #[max_len(16)]
-    pub kol_d: String,
-}
\ No newline at end of file
+    pub ticker: String,
+    #[max_len(32)]
+    pub name: String,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub decimals: u8,
+    pub status: KolStatus,
+    // mint supply right after the initial mint, guaranteed once the supply is locked
+    pub max_supply: u64,
+    // set by lock_kol_supply once the admin no longer holds the mint authority
+    pub supply_locked: bool,
+    // vault inventory: put into the vault, committed to packs, and paid out by claims
+    pub deposited: u64,
+    pub allocated: u64,
+    pub claimed: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    // GlobalPackPool::authority, handed over with propose/accept_authority rather than granted
+    Authority,
+    // commit_pack, recommit_pack, cancel_pack and pack funding
+    Operator,
+    // KOL mint registration, metadata and supply locking
+    Minter,
+    // may set pause flags, only the authority can lift them
+    Pauser,
+    // posts KOL PnL readings with record_kol_pnl
+    Oracle,
+}
+
+impl Role {
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+// seeds = [b"role", pool, member]; the roles granted to one key, as a bitmask of Role::bit
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    pub bump: u8,
+    pub member: Pubkey,
+    pub roles: u8,
+}
+
+impl RoleAssignment {
+    pub fn has(&self, role: Role) -> bool {
+        self.roles & role.bit() != 0
+    }
+}
+
+impl GlobalPackPool {
+    // the authority holds every role, anyone else needs it granted in their RoleAssignment
+    pub fn has_role(&self, signer: &Pubkey, assignment: Option<&RoleAssignment>, role: Role) -> bool {
+        *signer == self.authority
+            || (role != Role::Authority && assignment.is_some_and(|assignment| assignment.member == *signer && assignment.has(role)))
+    }
+}
+
+impl KolRecord {
+    // commits vault tokens to a pack, never more than the vault's uncommitted balance
+    pub fn allocate(&mut self, amount: u64) -> Result<()> {
+        let allocated = self.allocated.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(allocated <= self.deposited, ErrorCode::VaultOverAllocated);
+        self.allocated = allocated;
+        Ok(())
+    }
+
+    // hands back a commitment that was never moved into a pack
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.allocated = self.allocated.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+// seeds = [b"distributor", pool, id.to_le_bytes()]; id comes from GlobalPackPool::distributor_count.
+// pays out (user, kol, amount) allocations straight from the KOL vaults, without pack accounts
+#[account]
+#[derive(InitSpace)]
+pub struct Distributor {
+    pub id: u64,
+    pub bump: u8,
+    pub merkle_root: [u8; 32],
+    pub num_leaves: u32,
+    pub claimed_count: u32,
+    pub total_claimed: u64,
+    // vault tokens set aside per KOL when the distributor is created, one entry per mint
+    #[max_len(8)]
+    pub reserves: Vec<DistributorReserve>,
+    // one bit per leaf index, sized from num_leaves at creation
+    #[max_len(0)]
+    pub claimed_bitmap: Vec<u8>,
+}
+
+impl Distributor {
+    pub fn space(num_leaves: u32) -> usize {
+        8 + Self::INIT_SPACE + (num_leaves as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed_bitmap[index as usize / 8] |= 1 << (index % 8);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DistributorReserve {
+    pub mint: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+}
+
+// seeds = [b"vesting", pack, mint]; holds one claimed pack slot for its user, the tokens
+// sit in the escrow's ATA until withdraw_vested releases them
+#[account]
+#[derive(InitSpace)]
+pub struct VestingEscrow {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub pack: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub withdrawn: u64,
+    pub start_ts: i64,
+    pub schedule: VestingConfig,
+}
+
+// seeds = [b"kol_performance", kol_record]; latest PnL reading posted by the oracle
+#[account]
+#[derive(InitSpace)]
+pub struct KolPerformance {
+    pub bump: u8,
+    pub kol_record: Pubkey,
+    // oracle reporting period, strictly increasing
+    pub epoch: u64,
+    // realized PnL as reported by the oracle, signed
+    pub pnl: i64,
+    // when the oracle took the reading
+    pub timestamp: i64,
+    pub readings: u32,
+}
+
+// seeds = [b"kol_pnl", kol_record, epoch.to_le_bytes()]; immutable copy of every reading,
+// score_pack reads the one at a season's end_epoch
+#[account]
+#[derive(InitSpace)]
+pub struct KolPnlReading {
+    pub bump: u8,
+    pub kol_record: Pubkey,
+    pub epoch: u64,
+    pub pnl: i64,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct PackScore {
+    pub season: u32,
+    // sum over slots of the KOL's PnL change across the season times whole tokens in the slot
+    pub value: i64,
+    pub scored_at: i64,
+}
+
+// seeds = [b"leaderboard", pool, season.to_le_bytes()]; best scored packs of a season,
+// packs are scored on the KOL readings between start_epoch and end_epoch, so only once the season is over
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub bump: u8,
+    pub season: u32,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+    // sorted by score, highest first
+    #[max_len(10)]
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LeaderboardEntry {
+    // NFT packs change hands, so the owner is resolved from the pack when paying out
+    pub pack: Pubkey,
+    pub score: i64,
+}
+
+impl Leaderboard {
+    // re-scoring a pack replaces its previous entry
+    pub fn submit(&mut self, entry: LeaderboardEntry) {
+        self.entries.retain(|existing| existing.pack != entry.pack);
+        let position = self.entries.iter().position(|existing| entry.score > existing.score).unwrap_or(self.entries.len());
+        if position < LEADERBOARD_SIZE {
+            self.entries.insert(position, entry);
+            self.entries.truncate(LEADERBOARD_SIZE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raise() -> RaiseConfig {
+        RaiseConfig {
+            start_ts: 100,
+            end_ts: 200,
+            soft_cap: 1_000,
+            hard_cap: 5_000,
+            min_per_wallet: 1,
+            max_per_wallet: 1_000,
+        }
+    }
+
+    #[test]
+    fn raise_fails_only_after_the_window_below_the_soft_cap() {
+        let raise = raise();
+        // still open, even if nothing was raised yet
+        assert!(!raise.has_failed(150, 0));
+        assert!(!raise.has_failed(199, 999));
+        // closed below the soft cap
+        assert!(raise.has_failed(200, 999));
+        assert!(raise.has_failed(10_000, 0));
+        // closed at or above the soft cap
+        assert!(!raise.has_failed(200, 1_000));
+        assert!(!raise.has_failed(200, 5_000));
+    }
+}
@@ -7,9 +7,12 @@ import {
   getOrCreateAssociatedTokenAccount, 
   mintTo, 
   getAccount,
+  getMint,
+  transferChecked,
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync 
 } from "@solana/spl-token";
+import { createHash } from "crypto";
 import adminKeypair from "./admin.json";
 
 // Use explicit provider options to reduce stale blockhash issues
@@ -50,6 +53,13 @@ function kv(label: string, value: string | number | boolean) {
   console.log(`  • ${label}: ${value}`);
 }
 
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+function metadataPda(mint: PublicKey, edition = false): PublicKey {
+  const seeds = [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()];
+  if (edition) seeds.push(Buffer.from("edition"));
+  return PublicKey.findProgramAddressSync(seeds, TOKEN_METADATA_PROGRAM_ID)[0];
+}
+
 
 describe("pnlpackprogram", () => {
   const admin = anchor.web3.Keypair.fromSecretKey(new Uint8Array(adminKeypair));
@@ -58,6 +68,44 @@ describe("pnlpackprogram", () => {
   const connection = provider.connection;
 
   let mintAddress: PublicKey;
+  // every KOL registered so far, pack_reveal may draw any of them
+  const mintByTicker: Record<string, PublicKey> = {};
+
+  // mirrors randomness::derive_seed + draw_distinct so the client can pass the right KolRecords
+  async function previewDraw(packPda: PublicKey, revealSlot: number, packId: anchor.BN, size: number): Promise<string[]> {
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    // only KOLs registered before the commit are drawn
+    const registry = pool.kolRegistry.slice(0, (await program.account.pack.fetch(packPda)).registryLen);
+    const sysvar = await connection.getAccountInfo(anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY, "confirmed");
+    const count = Number(sysvar!.data.readBigUInt64LE(0));
+    let slotHash: Buffer | null = null;
+    for (let i = 0; i < count; i++) {
+      const offset = 8 + i * 40;
+      if (Number(sysvar!.data.readBigUInt64LE(offset)) === revealSlot) {
+        slotHash = sysvar!.data.subarray(offset + 8, offset + 40);
+        break;
+      }
+    }
+    if (!slotHash) throw new Error(`slot ${revealSlot} not found in SlotHashes`);
+
+    const seed = createHash("sha256")
+      .update(Buffer.concat([slotHash, packPda.toBuffer(), packId.toArrayLike(Buffer, "le", 8)]))
+      .digest();
+    const indexes = registry.map((_: string, i: number) => i);
+    for (let i = 0; i < size; i++) {
+      const roll = createHash("sha256")
+        .update(Buffer.concat([seed, new anchor.BN(i).toArrayLike(Buffer, "le", 8)]))
+        .digest();
+      const r = roll.readBigUInt64LE(0);
+      const j = i + Number(r % BigInt(indexes.length - i));
+      [indexes[i], indexes[j]] = [indexes[j], indexes[i]];
+    }
+    return indexes.slice(0, size).map((i: number) => registry[i]);
+  }
   let adminTokenAccount: PublicKey;
 
   it("Is initialized!", async () => {
@@ -73,8 +121,19 @@ describe("pnlpackprogram", () => {
     const [globalPackPoolAccount] =  anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("global_pack_pool")], program.programId)
     kv("Global Pack Pool PDA", globalPackPoolAccount.toString());
 
+    const now = Math.floor(Date.now() / 1000);
+    const raise = {
+      startTs: new anchor.BN(now - 60),
+      endTs: new anchor.BN(now + 3600),
+      softCap: new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+      hardCap: new anchor.BN(100 * LAMPORTS_PER_SOL),
+      minPerWallet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+      maxPerWallet: new anchor.BN(10 * LAMPORTS_PER_SOL),
+    };
+    kv("Raise window", `${raise.startTs.toString()} -> ${raise.endTs.toString()}`);
+
     step("Send init_global_pack_pool");
-    const tx = await program.methods.initGlobalPackPool(total_kols)
+    const tx = await program.methods.initGlobalPackPool(total_kols, raise)
     .accountsPartial({
       admin: admin.publicKey,
       globalPackPool: globalPackPoolAccount
@@ -104,6 +163,89 @@ describe("pnlpackprogram", () => {
 
     const balanceAfter = await connection.getBalance(globalPackPoolAccount)
     kv("Treasury balance (after, SOL)", balanceAfter/LAMPORTS_PER_SOL);
+
+    step("Verify contribution ledger");
+    const [contributionAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("contribution"), globalPackPoolAccount.toBuffer(), admin.publicKey.toBuffer()],
+      program.programId
+    );
+    const contribution = await program.account.contribution.fetch(contributionAccount);
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    kv("Contribution (lamports)", contribution.lamports.toString());
+    kv("Contribution count", contribution.contributionCount);
+    kv("Pool total raised (lamports)", pool.totalRaised.toString());
+    kv("Pool contributors", pool.totalContributors);
+    if (contribution.lamports.toString() !== amount.toString()) {
+      throw new Error(`contribution mismatch; expected ${amount.toString()}, got ${contribution.lamports.toString()}`);
+    }
+
+    step("Contribution above the per-wallet maximum is rejected");
+    let rejected = "";
+    try {
+      await program.methods.transferToPackPool(new anchor.BN(11 * LAMPORTS_PER_SOL))
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, user: admin.publicKey })
+        .signers([admin])
+        .rpc({commitment: "confirmed"});
+    } catch (e: any) {
+      rejected = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", rejected);
+    if (rejected !== "ContributionAboveMaximum") throw new Error("contribution above per-wallet max should fail");
+  });
+
+  it("[TEST3] claim_refund is rejected while the raise is still open", async () => {
+    banner("TEST3: REFUND BEFORE RAISE CLOSES");
+    const [globalPackPoolAccount] =  anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("global_pack_pool")], program.programId)
+
+    step("Send claim_refund");
+    let rejected = "";
+    try {
+      await program.methods.claimRefund()
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, user: admin.publicKey })
+        .signers([admin])
+        .rpc({commitment: "confirmed"});
+    } catch (e: any) {
+      rejected = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", rejected);
+    if (rejected !== "RefundUnavailable") throw new Error("refund should be unavailable during the raise");
+  });
+
+  it("[TEST4] Configures the fee split and withdraws from the treasury", async () => {
+    banner("TEST4: FEE SPLIT + WITHDRAW TREASURY");
+    const [globalPackPoolAccount] =  anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("global_pack_pool")], program.programId)
+    const team = anchor.web3.Keypair.generate().publicKey;
+    const kolShare = anchor.web3.Keypair.generate().publicKey;
+    const liquidity = anchor.web3.Keypair.generate().publicKey;
+    const feeRecipients = [
+      { recipient: team, kind: { team: {} }, bps: 5000 },
+      { recipient: kolShare, kind: { kolRevenueShare: {} }, bps: 3000 },
+      { recipient: liquidity, kind: { liquidityReserve: {} }, bps: 2000 },
+    ];
+
+    step("Send set_fee_recipients");
+    await program.methods.setFeeRecipients(feeRecipients)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+      .signers([admin])
+      .rpc({commitment: "confirmed"});
+
+    const amount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    step("Send withdraw_treasury");
+    const tx = await program.methods.withdrawTreasury(amount)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+      .remainingAccounts(feeRecipients.map((r) => ({ pubkey: r.recipient, isWritable: true, isSigner: false })))
+      .signers([admin])
+      .rpc({commitment: "confirmed"});
+    kv("Tx", tx);
+
+    for (const r of feeRecipients) {
+      const balance = await connection.getBalance(r.recipient);
+      const expected = (amount.toNumber() * r.bps) / 10000;
+      kv(`Payout ${r.recipient.toString()}`, balance);
+      if (balance !== expected) {
+        throw new Error(`payout mismatch; expected ${expected}, got ${balance}`);
+      }
+    }
   });
 
   it("[SETUP] Create test token mint and mint 1B tokens to admin", async () => {
@@ -196,12 +338,14 @@ describe("pnlpackprogram", () => {
     step("Execute super combined instruction");
     const tx = await program.methods.mintAndInitKolTokenVaultAndTransfer(
         kolTicker, 
+        "Super KOL",
         totalSupply, 
         vaultTransferAmount
       )
       .accountsPartial({
         globalPackPool: globalPackPoolAccount,
         admin: admin.publicKey,
+        role: null,
         mint: newMintKeypair.publicKey,
         adminTokenAccount: adminTokenAccountInfo.address,
         tokenVault: tokenVaultAccount,
@@ -215,6 +359,7 @@ describe("pnlpackprogram", () => {
     const endTime = Date.now();
     kv("Tx", tx);
     kv("Exec time (ms)", endTime - startTime);
+    mintByTicker[kolTicker] = newMintKeypair.publicKey;
     
     const adminBalanceAfter = await getAccount(connection, adminTokenAccountInfo.address);
     const expectedAdminBalance = 60000000; // 60M tokens
@@ -240,7 +385,7 @@ describe("pnlpackprogram", () => {
     kv("All verifications", allChecksPassed ? "PASSED ✅" : "FAILED ❌");
   });
 
-  it("[TEST9] pack_reveal initializes pack PDA and four ATAs, then transfers 40k each(total 160K)", async () => {
+  it("[TEST9] commit_pack + pack_reveal draw five KOLs, then transfers 40k each(total 200K)", async () => {
     banner("TEST9: PACK REVEAL + TRANSFER 40K EACH TO PACK ATAs");
     const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("global_pack_pool")],
@@ -249,9 +394,9 @@ describe("pnlpackprogram", () => {
     kv("Global Pack Pool PDA", globalPackPoolAccount.toString());
 
     // Prepare four KOLs and mints, initialize their vaults using the combined instruction
-    const kols = ["KOLA", "KOLB", "KOLC", "KOLD"]; // short tickers
-    const kolMints: PublicKey[] = [];
-    const kolVaults: PublicKey[] = [];
+    let kols = ["KOLA", "KOLB", "KOLC", "KOLD"]; // short tickers
+    let kolMints: PublicKey[] = [];
+    let kolVaults: PublicKey[] = [];
 
     banner("PHASE A: SETUP 4 KOL MINTS + VAULTS (MINT + INIT + TRANSFER)");
     for (const kol of kols) {
@@ -278,10 +423,11 @@ describe("pnlpackprogram", () => {
       const toVault = new anchor.BN("900000000000"); // 900,000 * 10^6
       step(`Super-combined: mint+init+transfer for ${kol}`);
       const tx = await program.methods
-        .mintAndInitKolTokenVaultAndTransfer(kol, supply, toVault)
+        .mintAndInitKolTokenVaultAndTransfer(kol, `${kol} KOL`, supply, toVault)
         .accountsPartial({
           globalPackPool: globalPackPoolAccount,
           admin: admin.publicKey,
+          role: null,
           mint: mintKeypair.publicKey,
           adminTokenAccount: adminAta.address,
           tokenVault: tokenVaultPda,
@@ -296,50 +442,143 @@ describe("pnlpackprogram", () => {
 
       kolMints.push(mintKeypair.publicKey);
       kolVaults.push(tokenVaultPda);
+      mintByTicker[kol] = mintKeypair.publicKey;
     }
 
-    banner("PHASE B: DERIVE PACK PDA + ATAs, THEN CALL pack_reveal");
-    step("Derive pack PDA");
+    banner("PHASE B: COMMIT A PACK LISTED FOR SALE");
+    step("Derive pack PDA from the next pack id");
+    const poolBeforeCommit = await program.account.globalPackPool.fetch(globalPackPoolAccount);
     const [packPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("pack"),
-        Buffer.from(kols[0]),
-        Buffer.from(kols[1]),
-        Buffer.from(kols[2]),
-        Buffer.from(kols[3]),
-      ],
+      [Buffer.from("pack"), poolBeforeCommit.packCount.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
+    kv("Pack id", poolBeforeCommit.packCount.toString());
     kv("Pack PDA", packPda.toString());
 
-    const packAtas = kolMints.map((mint, idx) => {
-      const ata = getAssociatedTokenAddressSync(mint, packPda, true, TOKEN_PROGRAM_ID);
-      kv(`Expected Pack ATA [${idx}]`, ata.toString());
-      return ata;
-    });
+    // SUPER from TEST8 plus the four KOLs above are registered, draw all five
+    const packSize = 5;
+    step("Call commit_pack (unassigned, listed for sale)");
+    await retryRpc(() =>
+      program.methods
+        .commitPack(PublicKey.default, packSize)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, packAccount: packPda })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    );
+
+    step("A listed pack stays sealed until it is bought");
+    let sealed = "";
+    try {
+      await program.methods
+        .packReveal()
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, packAccount: packPda, slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY })
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      sealed = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", sealed);
+    if (sealed !== "PackNotSold") throw new Error("listed packs must not be revealed before the sale");
+
+    banner("PHASE B2: SET PACK PRICE + BUY PACK");
+    const packPrice = new anchor.BN(0.02 * LAMPORTS_PER_SOL);
+    step("Send set_pack_price");
+    await program.methods.setPackPrice(packPrice)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    const [collectionMint] = PublicKey.findProgramAddressSync([Buffer.from("pack_collection")], program.programId);
+    if (!(await connection.getAccountInfo(collectionMint, "confirmed"))) {
+      step("Send init_pack_collection");
+      await retryRpc(() =>
+        program.methods.initPackCollection("Glympse Pack", "GPACK", "https://glympse.fun/packs.json")
+          .accountsPartial({
+            globalPackPool: globalPackPoolAccount,
+            admin: admin.publicKey,
+            collectionMint,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+    }
+
+    step("Send buy_pack");
+    const [packMint] = PublicKey.findProgramAddressSync([Buffer.from("pack_mint"), packPda.toBuffer()], program.programId);
+    const buyerPackNftAccount = getAssociatedTokenAddressSync(packMint, admin.publicKey, false, TOKEN_PROGRAM_ID);
+    const buyAccounts = {
+      globalPackPool: globalPackPoolAccount,
+      packAccount: packPda,
+      buyer: admin.publicKey,
+      packMint,
+      buyerPackTokenAccount: buyerPackNftAccount,
+      collectionMint,
+      collectionMetadata: metadataPda(collectionMint),
+      collectionMasterEdition: metadataPda(collectionMint, true),
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    };
+    const poolBalanceBeforeSale = await connection.getBalance(globalPackPoolAccount);
+    const revenueBeforeSale = (await program.account.globalPackPool.fetch(globalPackPoolAccount)).salesRevenue;
+    await retryRpc(() =>
+      program.methods.buyPack()
+        .accountsPartial(buyAccounts)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    );
+    const poolBalanceAfterSale = await connection.getBalance(globalPackPoolAccount);
+    const packAfterSale = await program.account.pack.fetch(packPda);
+    kv("Pack owner", packAfterSale.owner.toString());
+    kv("Pool received (lamports)", poolBalanceAfterSale - poolBalanceBeforeSale);
+    if (!packAfterSale.owner.equals(admin.publicKey)) throw new Error("buyer was not assigned as pack owner");
+    if (poolBalanceAfterSale - poolBalanceBeforeSale !== packPrice.toNumber()) throw new Error("pack price not charged");
+    const revenueAfterSale = (await program.account.globalPackPool.fetch(globalPackPoolAccount)).salesRevenue;
+    if (!revenueAfterSale.sub(revenueBeforeSale).eq(packPrice)) throw new Error("sale revenue not tracked");
+    if (!packAfterSale.nftMint?.equals(packMint)) throw new Error("pack NFT mint not recorded");
+    const packNft = await getAccount(connection, buyerPackNftAccount, "confirmed");
+    if (packNft.amount !== BigInt(1)) throw new Error("buyer did not receive the pack NFT");
+
+    step("Second buy_pack is rejected");
+    let resold = "";
+    try {
+      await program.methods.buyPack()
+        .accountsPartial(buyAccounts)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      resold = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", resold);
+    if (resold !== "PackAlreadySold") throw new Error("pack must not be sold twice");
+
+    banner("PHASE B3: WAIT FOR THE SLOT COMMITTED AT PURCHASE, THEN CALL pack_reveal");
+    const committed = await program.account.pack.fetch(packPda);
+    kv("Reveal slot", committed.revealSlot.toString());
+    while ((await connection.getSlot("confirmed")) <= committed.revealSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    step("Derive KolRecords for the drawn KOLs");
+    // the draw is deterministic once the reveal slot has passed; the program checks
+    // that these records match the KOLs it draws
+    const kolRecordFor = (kol: string) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kol)],
+        program.programId
+      )[0];
+    const drawnKols = await previewDraw(packPda, committed.revealSlot.toNumber(), committed.id, packSize);
+    kv("Expected draw", drawnKols.join(", "));
 
     step("Call pack_reveal");
     try {
       const tx = await retryRpc(() =>
         program.methods
-          .packReveal(kols[0], kols[1], kols[2], kols[3])
+          .packReveal()
           .accountsPartial({
             globalPackPool: globalPackPoolAccount,
-            admin: admin.publicKey,
             packAccount: packPda,
-            mintKolA: kolMints[0],
-            packKolATa: packAtas[0],
-            mintKolB: kolMints[1],
-            packKolBTa: packAtas[1],
-            mintKolC: kolMints[2],
-            packKolCTa: packAtas[2],
-            mintKolD: kolMints[3],
-            packKolDTa: packAtas[3],
-            systemProgram: anchor.web3.SystemProgram.programId,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+            slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
           })
-          .signers([admin])
+          .remainingAccounts(drawnKols.map((kol) => ({ pubkey: kolRecordFor(kol), isWritable: true, isSigner: false })))
           .rpc({ commitment: "confirmed" })
       );
       kv("pack_reveal tx", tx);
@@ -349,9 +588,28 @@ describe("pnlpackprogram", () => {
       throw e;
     }
 
+    const revealed = await program.account.pack.fetch(packPda);
+    if (!("revealed" in revealed.status)) throw new Error("pack was not revealed");
+    kols = revealed.slots.map((slot: any) => slot.ticker);
+    kv("Drawn KOLs", kols.join(", "));
+    kv("Randomness seed", Buffer.from(revealed.randomnessSeed).toString("hex"));
+    kolMints = kols.map((kol) => mintByTicker[kol]);
+    kolVaults = kols.map((kol) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("token_vault"), Buffer.from(kol), globalPackPoolAccount.toBuffer()],
+        program.programId
+      )[0]
+    );
+
+    const packAtas = kolMints.map((mint, idx) => {
+      const ata = getAssociatedTokenAddressSync(mint, packPda, true, TOKEN_PROGRAM_ID);
+      kv(`Expected Pack ATA [${idx}]`, ata.toString());
+      return ata;
+    });
+
     banner("PHASE C: TRANSFER 40K OF EACH KOL TO PACK ATAs");
     const transferAmount = new anchor.BN("40000000000"); // 40,000 * 10^6
-    for (let i = 0; i < 4; i++) {
+    for (let i = 0; i < kols.length; i++) {
       const kol = kols[i];
       step(`Transfer 40k ${kol} -> pack ATA`);
       kv("Mint", kolMints[i].toString());
@@ -363,6 +621,8 @@ describe("pnlpackprogram", () => {
             .transferToIndividualPack(kol, transferAmount)
             .accountsPartial({
               globalPackPool: globalPackPoolAccount,
+              admin: admin.publicKey,
+              role: null,
               packAccount: packPda,
               kolMint: kolMints[i],
               kolTokenVault: kolVaults[i],
@@ -382,7 +642,7 @@ describe("pnlpackprogram", () => {
     }
 
     banner("PHASE D: VERIFY PACK ATAs (owner/mint/balance)");
-    for (let i = 0; i < 4; i++) {
+    for (let i = 0; i < kols.length; i++) {
       step(`Verify Pack ATA[${i}]`);
       const ataInfo = await getAccount(connection, packAtas[i]);
       kv("Mint (actual)", ataInfo.mint.toString());
@@ -413,7 +673,7 @@ describe("pnlpackprogram", () => {
     // Balances before claim
     const userBalancesBefore: bigint[] = [];
     const packBalancesBefore: bigint[] = [];
-    for (let i = 0; i < 4; i++) {
+    for (let i = 0; i < kols.length; i++) {
       const uAcc = await getAccount(connection, userAtas[i]).catch(() => null); // might not exist yet
       userBalancesBefore.push(uAcc ? uAcc.amount : 0n);
       const pAcc = await getAccount(connection, packAtas[i]);
@@ -424,31 +684,31 @@ describe("pnlpackprogram", () => {
       step("Call claim_from_pack");
       const tx = await retryRpc(() =>
         program.methods
-          .claimFromPack(kols[0], kols[1], kols[2], kols[3], transferAmount)
+          .claimFromPack()
           .accountsPartial({
             pack: packPda,
             user: admin.publicKey,
-            // A
-            mintKolA: kolMints[0],
-            packKolATa: packAtas[0],
-            userKolATa: userAtas[0],
-            // B
-            mintKolB: kolMints[1],
-            packKolBTa: packAtas[1],
-            userKolBTa: userAtas[1],
-            // C
-            mintKolC: kolMints[2],
-            packKolCTa: packAtas[2],
-            userKolCTa: userAtas[2],
-            // D
-            mintKolD: kolMints[3],
-            packKolDTa: packAtas[3],
-            userKolDTa: userAtas[3],
-            // programs
+            // the pack was bought, so the claim burns its NFT
+            packNftMint: packMint,
+            userPackNftAccount: buyerPackNftAccount,
+            packNftMetadata: metadataPda(packMint),
+            packNftMasterEdition: metadataPda(packMint, true),
+            collectionMetadata: metadataPda(collectionMint),
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+            nftTokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           })
+          // per slot: KolRecord, mint, pack ATA, user ATA
+          .remainingAccounts(
+            kols.flatMap((kol, i) => [
+              { pubkey: kolRecordFor(kol), isWritable: true, isSigner: false },
+              { pubkey: kolMints[i], isWritable: false, isSigner: false },
+              { pubkey: packAtas[i], isWritable: true, isSigner: false },
+              { pubkey: userAtas[i], isWritable: true, isSigner: false },
+            ])
+          )
           .signers([admin])
           .rpc({ commitment: "confirmed" })
       );
@@ -460,7 +720,7 @@ describe("pnlpackprogram", () => {
     }
 
     banner("PHASE F: VERIFY USER + PACK BALANCES AFTER CLAIM");
-    for (let i = 0; i < 4; i++) {
+    for (let i = 0; i < kols.length; i++) {
       step(`Verify after claim [${i}]`);
       const userAcc = await getAccount(connection, userAtas[i]);
       const packAcc = await getAccount(connection, packAtas[i]);
@@ -480,9 +740,1123 @@ describe("pnlpackprogram", () => {
       kv(`Post-claim [${i}]`, "OK ✅");
     }
 
+    step("Verify pack NFT was burned");
+    if (await connection.getAccountInfo(buyerPackNftAccount, "confirmed")) throw new Error("pack NFT account should be closed by the burn");
+
+    step("Verify pack accounting after claim");
+    const claimedPack = await program.account.pack.fetch(packPda);
+    kv("Status", Object.keys(claimedPack.status)[0]);
+    kv("Claimed at", claimedPack.claimedAt.toString());
+    if (!("claimed" in claimedPack.status)) throw new Error("pack status should be Claimed");
+    claimedPack.slots.forEach((slot: any, i: number) => {
+      if (slot.claimed.toString() !== slot.allocated.toString()) {
+        throw new Error(`slot ${i} claimed ${slot.claimed.toString()} of ${slot.allocated.toString()}`);
+      }
+    });
+
+    step("Top up a claimed slot, the pack is funded again and its owner claims it without the burned NFT");
+    const topUp = new anchor.BN(1_000_000);
+    await retryRpc(() =>
+      program.methods
+        .transferToIndividualPack(kols[0], topUp)
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          admin: admin.publicKey,
+          role: null,
+          packAccount: packPda,
+          kolMint: kolMints[0],
+          kolTokenVault: kolVaults[0],
+          packKolTa: packAtas[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    );
+    const toppedUp = await program.account.pack.fetch(packPda);
+    if (!("funded" in toppedUp.status)) throw new Error("a topped-up pack should be Funded again");
+    if (toppedUp.nftMint !== null) throw new Error("the burned pack NFT should be cleared");
+    const userBeforeTopUp = await getAccount(connection, userAtas[0], "confirmed");
+    await retryRpc(() =>
+      program.methods
+        .claimFromPack()
+        .accountsPartial({ pack: packPda, user: admin.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts(
+          kols.flatMap((kol, i) => [
+            { pubkey: kolRecordFor(kol), isWritable: true, isSigner: false },
+            { pubkey: kolMints[i], isWritable: false, isSigner: false },
+            { pubkey: packAtas[i], isWritable: true, isSigner: false },
+            { pubkey: userAtas[i], isWritable: true, isSigner: false },
+          ])
+        )
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    );
+    const userAfterTopUp = await getAccount(connection, userAtas[0], "confirmed");
+    kv("Top-up claimed", (userAfterTopUp.amount - userBeforeTopUp.amount).toString());
+    if ((userAfterTopUp.amount - userBeforeTopUp.amount).toString() !== topUp.toString()) throw new Error("top-up was not claimed");
+    const reclaimed = await program.account.pack.fetch(packPda);
+    if (!("claimed" in reclaimed.status)) throw new Error("pack status should be Claimed again");
+
+    step("Verify KOL vault inventory after claim");
+    for (const kol of kols) {
+      const record = await program.account.kolRecord.fetch(kolRecordFor(kol));
+      kv(`${kol} deposited / allocated / claimed`, `${record.deposited} / ${record.allocated} / ${record.claimed}`);
+      if (record.allocated.gt(record.deposited)) throw new Error(`${kol} vault is over-allocated`);
+      if (record.claimed.lt(transferAmount)) throw new Error(`${kol} claim was not recorded`);
+    }
+
+    banner("PHASE G: CLOSE PACK + RECLAIM RENT");
+    step("Send 1 base unit to a claimed pack ATA, close_pack must still succeed");
+    const strayMintInfo = await getMint(connection, kolMints[0]);
+    await transferChecked(connection, admin, userAtas[0], kolMints[0], packAtas[0], admin, 1, strayMintInfo.decimals);
+    const vaultBeforeClose = await getAccount(connection, kolVaults[0]);
+    const payerBalanceBefore = await connection.getBalance(admin.publicKey);
+    await retryRpc(() =>
+      program.methods
+        .closePack()
+        .accountsPartial({ pack: packPda, payer: admin.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        // per slot: KolRecord, mint, pack ATA, KOL vault
+        .remainingAccounts(
+          kols.flatMap((kol, i) => [
+            { pubkey: kolRecordFor(kol), isWritable: true, isSigner: false },
+            { pubkey: kolMints[i], isWritable: false, isSigner: false },
+            { pubkey: packAtas[i], isWritable: true, isSigner: false },
+            { pubkey: kolVaults[i], isWritable: true, isSigner: false },
+          ])
+        )
+        .rpc({ commitment: "confirmed" })
+    );
+    const vaultAfterClose = await getAccount(connection, kolVaults[0]);
+    if (vaultAfterClose.amount !== vaultBeforeClose.amount + 1n) throw new Error("stray pack tokens should go back to the KOL vault");
+    const payerBalanceAfter = await connection.getBalance(admin.publicKey);
+    kv("Rent returned (lamports)", payerBalanceAfter - payerBalanceBefore);
+    if ((await connection.getAccountInfo(packPda)) !== null) throw new Error("pack account should be closed");
+    for (const ata of packAtas) {
+      if ((await connection.getAccountInfo(ata)) !== null) throw new Error(`pack ATA ${ata.toString()} should be closed`);
+    }
+
     banner("✅ TEST9 COMPLETE: PACK CREATED + FUNDED + CLAIMED TO USER (40K EACH)");
   });
 
-  
+  it("[TEST10] Hands program authority to a new key and back via propose/accept", async () => {
+    banner("TEST10: AUTHORITY HANDOVER (PROPOSE + ACCEPT)");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const newAuthority = anchor.web3.Keypair.generate();
+    kv("New authority", newAuthority.publicKey.toString());
+
+    step("admin proposes new authority");
+    await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    step("new authority accepts");
+    await program.methods
+      .acceptAuthority()
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, newAuthority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc({ commitment: "confirmed" });
+
+    let pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    kv("Authority after handover", pool.authority.toString());
+    if (!pool.authority.equals(newAuthority.publicKey)) {
+      throw new Error("authority was not handed over");
+    }
+
+    step("old admin can no longer propose");
+    let rejected = "";
+    try {
+      await program.methods
+        .proposeAuthority(admin.publicKey)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      rejected = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", rejected);
+    if (rejected !== "Unauthorized") throw new Error("old admin should have been rejected");
+
+    step("hand authority back to admin");
+    await program.methods
+      .proposeAuthority(admin.publicKey)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc({ commitment: "confirmed" });
+    await program.methods
+      .acceptAuthority()
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, newAuthority: admin.publicKey })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    kv("Authority restored", pool.authority.toString());
+    if (!pool.authority.equals(admin.publicKey)) {
+      throw new Error("authority was not restored to admin");
+    }
+  });
 
-});
\ No newline at end of file
+  it("[TEST11] Configures rarity tiers and rejects duplicate tiers", async () => {
+    banner("TEST11: SET PACK TIERS");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const table = (amounts: number[]) => ({
+      outcomes: amounts.map((amount) => ({ weight: 1, amount: new anchor.BN(amount) })),
+    });
+    const tiers = [
+      { tier: { common: {} }, weight: 80, slotTables: Array(8).fill(table([10_000_000_000, 20_000_000_000])), vesting: null },
+      { tier: { rare: {} }, weight: 15, slotTables: Array(8).fill(table([50_000_000_000])), vesting: null },
+      { tier: { legendary: {} }, weight: 5, slotTables: Array(8).fill(table([100_000_000_000, 250_000_000_000])), vesting: null },
+    ];
+
+    step("Duplicate tiers are rejected");
+    let rejected = "";
+    try {
+      await program.methods.setPackTiers([tiers[0], tiers[0]])
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      rejected = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", rejected);
+    if (rejected !== "InvalidTierConfig") throw new Error("duplicate tiers should be rejected");
+
+    step("Tiers without a table for every slot are rejected");
+    let short = "";
+    try {
+      await program.methods.setPackTiers([{ ...tiers[0], slotTables: tiers[0].slotTables.slice(0, 2) }])
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      short = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", short);
+    if (short !== "InvalidTierConfig") throw new Error("every tier needs a table per slot");
+
+    step("Send set_pack_tiers");
+    await program.methods.setPackTiers(tiers)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    kv("Tiers configured", pool.tiers.length);
+    if (pool.tiers.length !== 3) throw new Error("tiers were not stored");
+  });
+
+  it("[TEST12] Creates token metadata for a KOL mint and updates its URI", async () => {
+    banner("TEST12: KOL TOKEN METADATA");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const kolTicker = "SUPER";
+    const mint = mintByTicker[kolTicker];
+    const metadata = metadataPda(mint);
+
+    step("Send create_kol_metadata");
+    await program.methods.createKolMetadata(kolTicker, kolTicker, "https://glympse.fun/kols/super.json")
+      .accountsPartial({
+        globalPackPool: globalPackPoolAccount,
+        admin: admin.publicKey,
+        role: null,
+        mint,
+        metadata,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const created = await connection.getAccountInfo(metadata, "confirmed");
+    if (!created) throw new Error("metadata account was not created");
+    if (!created.data.includes(Buffer.from("super.json"))) throw new Error("metadata URI not stored");
+
+    step("A symbol over 10 bytes is rejected");
+    let tooLong = "";
+    try {
+      await program.methods.updateKolMetadata(kolTicker, "SUPERLONGSYM", "https://glympse.fun/kols/super.json")
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          admin: admin.publicKey,
+          role: null,
+          metadata,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      tooLong = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", tooLong);
+    if (tooLong !== "NftMetadataTooLong") throw new Error("symbols longer than the metadata limit should be rejected");
+
+    step("Send update_kol_metadata");
+    await program.methods.updateKolMetadata(kolTicker, kolTicker, "https://glympse.fun/kols/super-v2.json")
+      .accountsPartial({
+        globalPackPool: globalPackPoolAccount,
+        admin: admin.publicKey,
+        role: null,
+        metadata,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const updated = await connection.getAccountInfo(metadata, "confirmed");
+    if (!updated!.data.includes(Buffer.from("super-v2.json"))) throw new Error("metadata URI not updated");
+  });
+
+  it("[TEST13] Locks a KOL supply by handing the mint authority to the pool", async () => {
+    banner("TEST13: LOCK KOL SUPPLY");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const kolTicker = "SUPER";
+    const mint = mintByTicker[kolTicker];
+    const [kolRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kolTicker)],
+      program.programId
+    );
+    const lock = () =>
+      program.methods.lockKolSupply(kolTicker, false)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, kolRecord, mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    step("Send lock_kol_supply");
+    await lock();
+    const record = await program.account.kolRecord.fetch(kolRecord);
+    const mintInfo = await getMint(connection, mint, "confirmed");
+    kv("Max supply", record.maxSupply.toString());
+    kv("Mint authority", String(mintInfo.mintAuthority));
+    if (!record.supplyLocked) throw new Error("supply was not marked locked");
+    if (record.maxSupply.toString() !== mintInfo.supply.toString()) throw new Error("max supply does not match the mint");
+    if (!mintInfo.mintAuthority?.equals(globalPackPoolAccount)) throw new Error("mint authority was not handed to the pool");
+
+    step("Second lock is rejected");
+    let relocked = "";
+    try {
+      await lock();
+    } catch (e: any) {
+      relocked = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", relocked);
+    if (relocked !== "KolSupplyLocked") throw new Error("supply must not be locked twice");
+  });
+
+  it("[TEST14] Funds every slot of a tiered pack with batch_transfer_to_packs, claims wait for the full targets", async () => {
+    banner("TEST14: BATCH FUNDING");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const pda = (seeds: Buffer[]) => anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const kolRecordFor = (kol: string) => pda([Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kol)]);
+    const vaultFor = (kol: string) => pda([Buffer.from("token_vault"), Buffer.from(kol), globalPackPoolAccount.toBuffer()]);
+
+    step("Commit and reveal a two-KOL pack");
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    const packPda = pda([Buffer.from("pack"), pool.packCount.toArrayLike(Buffer, "le", 8)]);
+    const packSize = 2;
+    let oversized = "";
+    try {
+      await program.methods
+        .commitPack(admin.publicKey, pool.kolRegistry.length + 1)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, packAccount: packPda })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      oversized = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Pack larger than the registry rejected with", oversized);
+    if (oversized !== "NotEnoughKols") throw new Error("packs larger than the KOL registry must not be committed");
+    await retryRpc(() =>
+      program.methods
+        .commitPack(admin.publicKey, packSize)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, packAccount: packPda })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    );
+    const committed = await program.account.pack.fetch(packPda);
+    while ((await connection.getSlot("confirmed")) <= committed.revealSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    let rerolled = "";
+    try {
+      await program.methods.recommitPack()
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          admin: admin.publicKey,
+          role: null,
+          packAccount: packPda,
+          slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      rerolled = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Re-commit of a revealable pack rejected with", rerolled);
+    if (rerolled !== "RevealSlotNotExpired") throw new Error("a pack must not be re-committed while its slot hash is available");
+    const drawnKols = await previewDraw(packPda, committed.revealSlot.toNumber(), committed.id, packSize);
+    await retryRpc(() =>
+      program.methods
+        .packReveal()
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          packAccount: packPda,
+          slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .remainingAccounts(drawnKols.map((kol) => ({ pubkey: kolRecordFor(kol), isWritable: true, isSigner: false })))
+        .rpc({ commitment: "confirmed" })
+    );
+    const revealed = await program.account.pack.fetch(packPda);
+
+    step("A revealed pack can't be bought");
+    const [collectionMint] = PublicKey.findProgramAddressSync([Buffer.from("pack_collection")], program.programId);
+    let opened = "";
+    try {
+      await program.methods.buyPack()
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          packAccount: packPda,
+          buyer: admin.publicKey,
+          packMint: pda([Buffer.from("pack_mint"), packPda.toBuffer()]),
+          buyerPackTokenAccount: getAssociatedTokenAddressSync(
+            pda([Buffer.from("pack_mint"), packPda.toBuffer()]),
+            admin.publicKey,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          collectionMint,
+          collectionMetadata: metadataPda(collectionMint),
+          collectionMasterEdition: metadataPda(collectionMint, true),
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      opened = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", opened);
+    if (opened !== "PackAlreadyRevealed") throw new Error("buyers must not pay for a pack they can already see");
+
+    const entries = revealed.slots.map((slot: any) => ({ kol: slot.ticker, amount: slot.target }));
+    entries.forEach((entry: any) => kv(`${entry.kol} target`, entry.amount.toString()));
+
+    const batchTransfer = (batch: any[], slots: any[]) =>
+      retryRpc(() =>
+        program.methods
+          .batchTransferToPacks(batch)
+          .accountsPartial({
+            globalPackPool: globalPackPoolAccount,
+            admin: admin.publicKey,
+            role: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          })
+          // per entry: pack, KolRecord, mint, vault, pack ATA
+          .remainingAccounts(
+            slots.flatMap((slot: any) => [
+              { pubkey: packPda, isWritable: true, isSigner: false },
+              { pubkey: kolRecordFor(slot.ticker), isWritable: true, isSigner: false },
+              { pubkey: slot.mint, isWritable: false, isSigner: false },
+              { pubkey: vaultFor(slot.ticker), isWritable: true, isSigner: false },
+              { pubkey: getAssociatedTokenAddressSync(slot.mint, packPda, true, TOKEN_PROGRAM_ID), isWritable: true, isSigner: false },
+            ])
+          )
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    step("Fund one base unit of the first slot, the partly funded pack can't be claimed");
+    const [first] = revealed.slots;
+    await batchTransfer([{ kol: first.ticker, amount: new anchor.BN(1) }], [first]);
+    let partial = "";
+    try {
+      await program.methods.claimFromPack()
+        .accountsPartial({ pack: packPda, user: admin.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        // per slot: KolRecord, mint, pack ATA, user ATA
+        .remainingAccounts(
+          revealed.slots.flatMap((slot: any) => [
+            { pubkey: kolRecordFor(slot.ticker), isWritable: true, isSigner: false },
+            { pubkey: slot.mint, isWritable: false, isSigner: false },
+            { pubkey: getAssociatedTokenAddressSync(slot.mint, packPda, true, TOKEN_PROGRAM_ID), isWritable: true, isSigner: false },
+            { pubkey: getAssociatedTokenAddressSync(slot.mint, admin.publicKey, false, TOKEN_PROGRAM_ID), isWritable: true, isSigner: false },
+          ])
+        )
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      partial = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", partial);
+    if (partial !== "PackNotFullyFunded") throw new Error("a tiered pack must be funded to its targets before a claim");
+
+    step("Send batch_transfer_to_packs for the rest of every target");
+    await batchTransfer(
+      entries.map((entry: any, i: number) => (i === 0 ? { ...entry, amount: entry.amount.subn(1) } : entry)),
+      revealed.slots
+    );
+
+    const funded = await program.account.pack.fetch(packPda);
+    kv("Status", Object.keys(funded.status)[0]);
+    if (!("funded" in funded.status)) throw new Error("pack should be Funded");
+    for (const slot of funded.slots) {
+      const ata = await getAccount(connection, getAssociatedTokenAddressSync(slot.mint, packPda, true, TOKEN_PROGRAM_ID));
+      kv(`${slot.ticker} pack ATA`, ata.amount.toString());
+      if (ata.amount.toString() !== slot.target.toString()) throw new Error(`${slot.ticker} was not funded to its target`);
+      if (slot.allocated.toString() !== slot.target.toString()) throw new Error(`${slot.ticker} allocation not recorded`);
+    }
+  });
+
+  it("[TEST15] Claims a merkle distributor allocation straight from the KOL vault, once, then closes the distributor", async () => {
+    banner("TEST15: MERKLE DISTRIBUTOR");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const kol = "SUPER";
+    const mint = mintByTicker[kol];
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+    // mirrors merkle::leaf_hash / merkle::verify
+    const leaf = (index: number, user: PublicKey, amount: anchor.BN) => {
+      const idx = Buffer.alloc(4);
+      idx.writeUInt32LE(index);
+      return sha256(Buffer.from([0]), idx, user.toBuffer(), mint.toBuffer(), amount.toArrayLike(Buffer, "le", 8));
+    };
+    const node = (a: Buffer, b: Buffer) =>
+      Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+
+    const amount = new anchor.BN(1_000_000_000); // 1,000 * 10^6
+    const leaves = [leaf(0, admin.publicKey, amount), leaf(1, anchor.web3.Keypair.generate().publicKey, amount)];
+    const root = node(leaves[0], leaves[1]);
+
+    step("Send create_distributor");
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    const [distributor] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("distributor"), globalPackPoolAccount.toBuffer(), pool.distributorCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const kolRecord = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kol)],
+      program.programId
+    )[0];
+    const allocatedBefore = (await program.account.kolRecord.fetch(kolRecord)).allocated;
+    await program.methods.createDistributor(Array.from(root), leaves.length, [amount.muln(2)])
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, distributor })
+      .remainingAccounts([{ pubkey: kolRecord, isWritable: true, isSigner: false }])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const reserved = (await program.account.kolRecord.fetch(kolRecord)).allocated.sub(allocatedBefore);
+    kv("Reserved in the vault", reserved.toString());
+    if (!reserved.eq(amount.muln(2))) throw new Error("both leaves should be reserved at creation");
+
+    const [tokenVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("token_vault"), Buffer.from(kol), globalPackPoolAccount.toBuffer()],
+      program.programId
+    );
+    const userAta = getAssociatedTokenAddressSync(mint, admin.publicKey, false, TOKEN_PROGRAM_ID);
+    const claim = (proof: Buffer[]) =>
+      program.methods.claimFromDistributor(kol, 0, amount, proof.map((p) => Array.from(p)))
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          distributor,
+          kolMint: mint,
+          kolTokenVault: tokenVault,
+          user: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    step("A wrong proof is rejected");
+    let forged = "";
+    try {
+      await claim([leaves[0]]);
+    } catch (e: any) {
+      forged = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", forged);
+    if (forged !== "InvalidMerkleProof") throw new Error("claim with a bad proof must fail");
+
+    step("Send claim_from_distributor");
+    const before = await getAccount(connection, userAta, "confirmed");
+    await claim([leaves[1]]);
+    const after = await getAccount(connection, userAta, "confirmed");
+    kv("Received", (after.amount - before.amount).toString());
+    if ((after.amount - before.amount).toString() !== amount.toString()) throw new Error("distributor amount not paid");
+
+    step("Second claim of the same leaf is rejected");
+    let doubled = "";
+    try {
+      await claim([leaves[1]]);
+    } catch (e: any) {
+      doubled = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", doubled);
+    if (doubled !== "AlreadyClaimed") throw new Error("a leaf must not be claimed twice");
+    const state = await program.account.distributor.fetch(distributor);
+    if (state.claimedCount !== 1) throw new Error("claim was not recorded on the distributor");
+    if (!state.reserves[0].claimed.eq(amount)) throw new Error("claim was not counted against the reserve");
+    const allocatedAfter = (await program.account.kolRecord.fetch(kolRecord)).allocated;
+    if (!allocatedAfter.sub(allocatedBefore).eq(amount.muln(2))) throw new Error("claiming must not allocate twice");
+
+    step("Send close_distributor, the unclaimed leaf goes back to the vault");
+    await program.methods.closeDistributor()
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, distributor })
+      .remainingAccounts([{ pubkey: kolRecord, isWritable: true, isSigner: false }])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const allocatedClosed = (await program.account.kolRecord.fetch(kolRecord)).allocated;
+    kv("Still allocated to the distributor", allocatedClosed.sub(allocatedBefore).toString());
+    if (!allocatedClosed.sub(allocatedBefore).eq(amount)) throw new Error("only the claimed leaf should stay allocated");
+    if (await connection.getAccountInfo(distributor, "confirmed")) throw new Error("distributor should be closed");
+  });
+
+  it("[TEST16] Vests claimed tokens in an escrow and releases them with withdraw_vested", async () => {
+    banner("TEST16: VESTING ESCROW");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const pda = (seeds: Buffer[]) => anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const kolRecordFor = (kol: string) => pda([Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kol)]);
+    const vaultFor = (kol: string) => pda([Buffer.from("token_vault"), Buffer.from(kol), globalPackPoolAccount.toBuffer()]);
+    const setVesting = (vesting: any) =>
+      program.methods.setVesting(vesting)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    step("Send set_vesting (4s cliff, 12s linear, 4s period)");
+    await setVesting({ cliffSeconds: new anchor.BN(4), durationSeconds: new anchor.BN(12), periodSeconds: new anchor.BN(4) });
+
+    step("Commit, reveal and fund a one-KOL pack");
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    const packPda = pda([Buffer.from("pack"), pool.packCount.toArrayLike(Buffer, "le", 8)]);
+    await program.methods.commitPack(admin.publicKey, 1)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, packAccount: packPda })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const committed = await program.account.pack.fetch(packPda);
+    while ((await connection.getSlot("confirmed")) <= committed.revealSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    const [drawn] = await previewDraw(packPda, committed.revealSlot.toNumber(), committed.id, 1);
+    await program.methods.packReveal()
+      .accountsPartial({
+        globalPackPool: globalPackPoolAccount,
+        packAccount: packPda,
+        slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .remainingAccounts([{ pubkey: kolRecordFor(drawn), isWritable: true, isSigner: false }])
+      .rpc({ commitment: "confirmed" });
+    const revealed = await program.account.pack.fetch(packPda);
+    if (!revealed.vesting) throw new Error("pack should carry the pool vesting schedule");
+    const slot = revealed.slots[0];
+    const packAta = getAssociatedTokenAddressSync(slot.mint, packPda, true, TOKEN_PROGRAM_ID);
+    await program.methods.batchTransferToPacks([{ kol: slot.ticker, amount: slot.target }])
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts([
+        { pubkey: packPda, isWritable: true, isSigner: false },
+        { pubkey: kolRecordFor(slot.ticker), isWritable: true, isSigner: false },
+        { pubkey: slot.mint, isWritable: false, isSigner: false },
+        { pubkey: vaultFor(slot.ticker), isWritable: true, isSigner: false },
+        { pubkey: packAta, isWritable: true, isSigner: false },
+      ])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    step("Claim into the vesting escrow");
+    const escrow = pda([Buffer.from("vesting"), packPda.toBuffer(), slot.mint.toBuffer()]);
+    const escrowAta = getAssociatedTokenAddressSync(slot.mint, escrow, true, TOKEN_PROGRAM_ID);
+    await program.methods.claimFromPack()
+      .accountsPartial({ pack: packPda, user: admin.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      // per slot: KolRecord, mint, pack ATA, escrow, escrow ATA
+      .remainingAccounts([
+        { pubkey: kolRecordFor(slot.ticker), isWritable: true, isSigner: false },
+        { pubkey: slot.mint, isWritable: false, isSigner: false },
+        { pubkey: packAta, isWritable: true, isSigner: false },
+        { pubkey: escrow, isWritable: true, isSigner: false },
+        { pubkey: escrowAta, isWritable: true, isSigner: false },
+      ])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const escrowBalance = await getAccount(connection, escrowAta, "confirmed");
+    kv("Escrowed", escrowBalance.amount.toString());
+    if (escrowBalance.amount.toString() !== slot.target.toString()) throw new Error("claim should land in the escrow");
+
+    const userAta = getAssociatedTokenAddressSync(slot.mint, admin.publicKey, false, TOKEN_PROGRAM_ID);
+    const withdraw = () =>
+      program.methods.withdrawVested()
+        .accountsPartial({ escrow, user: admin.publicKey, mint: slot.mint, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    const withdrawn = async () => {
+      const before = await getAccount(connection, userAta, "confirmed");
+      await withdraw();
+      const after = await getAccount(connection, userAta, "confirmed");
+      return after.amount - before.amount;
+    };
+    const { startTs } = await program.account.vestingEscrow.fetch(escrow, "confirmed");
+    const waitUntil = async (ts: number) => {
+      while ((await connection.getBlockTime(await connection.getSlot("confirmed"))) < ts) {
+        await new Promise((resolve) => setTimeout(resolve, 500));
+      }
+    };
+
+    step("withdraw_vested before the cliff is rejected");
+    let cliffCode = "";
+    try {
+      await withdraw();
+    } catch (e: any) {
+      cliffCode = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", cliffCode);
+    if (cliffCode !== "NothingVested") throw new Error("nothing should vest before the cliff");
+
+    step("Past the cliff, withdraw_vested releases a partial tranche");
+    await waitUntil(startTs.toNumber() + 4);
+    const partial = await withdrawn();
+    kv("Released", partial.toString());
+    const total = BigInt(slot.target.toString());
+    // 1/3 or 2/3 of the total depending on which period the withdrawal landed in
+    if (partial !== total / 3n && partial !== (total * 2n) / 3n) throw new Error("partial release should follow the schedule");
+    const escrowState = await program.account.vestingEscrow.fetch(escrow, "confirmed");
+    if (escrowState.withdrawn.toString() !== partial.toString()) throw new Error("escrow should record the partial release");
+
+    step("Send 1 base unit to the escrow ATA, the final withdrawal must still close it");
+    const mintInfo = await getMint(connection, slot.mint);
+    await transferChecked(connection, admin, userAta, slot.mint, escrowAta, admin, 1, mintInfo.decimals);
+
+    step("Wait out the schedule, then send withdraw_vested");
+    await waitUntil(startTs.toNumber() + 12);
+    const rest = await withdrawn();
+    kv("Released", rest.toString());
+    if (rest !== total - partial + 1n) throw new Error("final tranche should sweep the escrow");
+    if (await connection.getAccountInfo(escrow, "confirmed")) throw new Error("fully withdrawn escrow should be closed");
+    if (await connection.getAccountInfo(escrowAta, "confirmed")) throw new Error("escrow ATA should be closed");
+
+    step("Turn vesting back off");
+    await setVesting(null);
+  });
+
+  it("[TEST17] Pause flags reject the paused instruction group until lifted", async () => {
+    banner("TEST17: PAUSE FLAGS");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const setPaused = (paused: { raise: boolean; reveal: boolean; funding: boolean; claims: boolean }) =>
+      program.methods.setPauseFlags(paused)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    step("Pause the raise");
+    await setPaused({ raise: true, reveal: false, funding: false, claims: false });
+    let code = "";
+    try {
+      await program.methods.transferToPackPool(new anchor.BN(0.01 * LAMPORTS_PER_SOL))
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, user: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      code = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", code);
+    if (code !== "ProgramPaused") throw new Error("contribution should be rejected while the raise is paused");
+
+    step("Lift the pause");
+    await setPaused({ raise: false, reveal: false, funding: false, claims: false });
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    if (pool.paused.raise) throw new Error("raise should be unpaused");
+  });
+
+  it("[TEST18] An operator key can commit packs but not touch authority-only settings", async () => {
+    banner("TEST18: ROLE-BASED ACCESS");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const operator = anchor.web3.Keypair.generate();
+    const [operatorRole] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), globalPackPoolAccount.toBuffer(), operator.publicKey.toBuffer()],
+      program.programId
+    );
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: operator.publicKey, lamports: 0.1 * LAMPORTS_PER_SOL })
+      ),
+      [admin]
+    );
+
+    step("Grant the Operator role");
+    await program.methods.grantRole(operator.publicKey, { operator: {} })
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, roleAssignment: operatorRole })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+
+    const commitAsOperator = async (role: PublicKey | null = operatorRole) => {
+      const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+      const [packPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pack"), pool.packCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      return program.methods.commitPack(admin.publicKey, 1)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: operator.publicKey, role, packAccount: packPda })
+        .signers([operator])
+        .rpc({ commitment: "confirmed" });
+    };
+    const rejection = async (fn: () => Promise<unknown>) => {
+      try {
+        await fn();
+      } catch (e: any) {
+        return String(e.error?.errorCode?.code ?? e);
+      }
+      return "";
+    };
+
+    step("Operator commits a pack");
+    await commitAsOperator();
+
+    step("Operator cannot change the pack price");
+    const priceCode = await rejection(() =>
+      program.methods.setPackPrice(new anchor.BN(1))
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: operator.publicKey })
+        .signers([operator])
+        .rpc({ commitment: "confirmed" })
+    );
+    kv("Rejected with", priceCode);
+    if (priceCode !== "Unauthorized") throw new Error("operator must not set the pack price");
+
+    step("Revoke the role, commit is rejected again");
+    await program.methods.revokeRole(operator.publicKey, { operator: {} })
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, roleAssignment: operatorRole })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    if (await connection.getAccountInfo(operatorRole, "confirmed")) throw new Error("empty role assignment should be closed");
+    // the role assignment is closed, so the operator can only sign without one
+    const revokedCode = await rejection(() => commitAsOperator(null));
+    kv("Rejected with", revokedCode);
+    if (revokedCode !== "Unauthorized") throw new Error("revoked operator must not commit packs");
+  });
+
+  it("[TEST19] Records KOL PnL readings with increasing epochs and bounded deltas", async () => {
+    banner("TEST19: KOL PNL ORACLE");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const kolTicker = "SUPER";
+    const [kolRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kolTicker)],
+      program.programId
+    );
+    const [kolPerformance] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("kol_performance"), kolRecord.toBuffer()],
+      program.programId
+    );
+
+    const setMaxPnlDelta = (maxPnlDelta: number) =>
+      program.methods.setMaxPnlDelta(new anchor.BN(maxPnlDelta))
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+    // the authority holds every role, including Oracle
+    const chainNow = (await connection.getBlockTime(await connection.getSlot("confirmed")))!;
+    const readingFor = (epoch: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("kol_pnl"), kolRecord.toBuffer(), new anchor.BN(epoch).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    const record = (epoch: number, pnl: number, timestamp: number) =>
+      program.methods.recordKolPnl(kolTicker, new anchor.BN(epoch), new anchor.BN(pnl), new anchor.BN(timestamp))
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          oracle: admin.publicKey,
+          role: null,
+          kolRecord,
+          kolPerformance,
+          kolPnlReading: readingFor(epoch),
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    const rejection = async (fn: () => Promise<unknown>) => {
+      try {
+        await fn();
+      } catch (e: any) {
+        return String(e.error?.errorCode?.code ?? e);
+      }
+      return "";
+    };
+
+    step("Record epochs 1 and 2 with no max delta configured");
+    await setMaxPnlDelta(0);
+    await record(1, 500, chainNow - 20);
+    await record(2, 5_000, chainNow - 10);
+    const performance = await program.account.kolPerformance.fetch(kolPerformance);
+    kv("Epoch / PnL", `${performance.epoch} / ${performance.pnl}`);
+    if (performance.epoch.toNumber() !== 2 || performance.pnl.toNumber() !== 5_000) throw new Error("reading not stored");
+    const firstReading = await program.account.kolPnlReading.fetch(readingFor(1));
+    if (firstReading.pnl.toNumber() !== 500) throw new Error("epoch 1 reading should be kept");
+
+    step("Replayed and stale epochs are rejected");
+    // a replayed epoch already has its KolPnlReading, so it fails on account creation
+    const replay = await rejection(() => record(2, 5_100, chainNow - 5));
+    kv("Replay rejected with", replay);
+    if (replay === "") throw new Error("epoch must not be replayed");
+    const stale = await rejection(() => record(0, 5_100, chainNow - 5));
+    kv("Stale rejected with", stale);
+    if (stale !== "PnlEpochNotIncreasing") throw new Error("epoch must increase");
+
+    step("Send set_max_pnl_delta, a jump beyond it is rejected");
+    await setMaxPnlDelta(1_000);
+    const jump = await rejection(() => record(3, 10_000, chainNow - 5));
+    kv("Rejected with", jump);
+    if (jump !== "PnlDeltaExceeded") throw new Error("delta must be bounded");
+  });
+
+  it("[TEST20] Scores a pack on its KOL's PnL change over the season and ranks it on the leaderboard", async () => {
+    banner("TEST20: PACK SCORING + LEADERBOARD");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const pda = (seeds: Buffer[]) => anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const kolRecordFor = (kol: string) => pda([Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kol)]);
+    const vaultFor = (kol: string) => pda([Buffer.from("token_vault"), Buffer.from(kol), globalPackPoolAccount.toBuffer()]);
+    const performanceFor = (kol: string) => pda([Buffer.from("kol_performance"), kolRecordFor(kol).toBuffer()]);
+    const readingFor = (kol: string, epoch: number) =>
+      pda([Buffer.from("kol_pnl"), kolRecordFor(kol).toBuffer(), new anchor.BN(epoch).toArrayLike(Buffer, "le", 8)]);
+    const leaderboardFor = (season: number) =>
+      pda([Buffer.from("leaderboard"), globalPackPoolAccount.toBuffer(), new anchor.BN(season).toArrayLike(Buffer, "le", 4)]);
+    const chainNow = async () => (await connection.getBlockTime(await connection.getSlot("confirmed")))!;
+    // readings need strictly increasing timestamps, wait for the chain clock to move past ts
+    const after = async (ts: number) => {
+      let now = await chainNow();
+      while (now <= ts) {
+        await new Promise((resolve) => setTimeout(resolve, 500));
+        now = await chainNow();
+      }
+      return now;
+    };
+
+    step("Commit and reveal a one-KOL pack");
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    const packPda = pda([Buffer.from("pack"), pool.packCount.toArrayLike(Buffer, "le", 8)]);
+    await program.methods.commitPack(admin.publicKey, 1)
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, packAccount: packPda })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const committed = await program.account.pack.fetch(packPda);
+    while ((await connection.getSlot("confirmed")) <= committed.revealSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    const [drawn] = await previewDraw(packPda, committed.revealSlot.toNumber(), committed.id, 1);
+    await program.methods.packReveal()
+      .accountsPartial({
+        globalPackPool: globalPackPoolAccount,
+        packAccount: packPda,
+        slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .remainingAccounts([{ pubkey: kolRecordFor(drawn), isWritable: true, isSigner: false }])
+      .rpc({ commitment: "confirmed" });
+    const [slot] = (await program.account.pack.fetch(packPda)).slots;
+    kv("Pack", packPda.toString());
+    kv("KOL", slot.ticker);
+
+    await program.methods.setMaxPnlDelta(new anchor.BN(0))
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const record = (epoch: number, pnl: number, timestamp: number) =>
+      program.methods.recordKolPnl(slot.ticker, new anchor.BN(epoch), new anchor.BN(pnl), new anchor.BN(timestamp))
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          oracle: admin.publicKey,
+          role: null,
+          kolRecord: kolRecordFor(slot.ticker),
+          kolPerformance: performanceFor(slot.ticker),
+          kolPnlReading: readingFor(slot.ticker, epoch),
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    const initLeaderboard = (season: number, startEpoch: number, endEpoch: number) =>
+      program.methods.initLeaderboard(season, new anchor.BN(startEpoch), new anchor.BN(endEpoch))
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, leaderboard: leaderboardFor(season) })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    const scorePack = (season: number, startEpoch: number, endEpoch: number) =>
+      program.methods.scorePack()
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, leaderboard: leaderboardFor(season), pack: packPda })
+        // per slot: KolRecord, KolPnlReading at start_epoch, KolPnlReading at end_epoch
+        .remainingAccounts([
+          { pubkey: kolRecordFor(slot.ticker), isWritable: false, isSigner: false },
+          { pubkey: readingFor(slot.ticker, startEpoch), isWritable: false, isSigner: false },
+          { pubkey: readingFor(slot.ticker, endEpoch), isWritable: false, isSigner: false },
+        ])
+        .rpc({ commitment: "confirmed" });
+    const rejection = async (fn: () => Promise<unknown>) => {
+      try {
+        await fn();
+      } catch (e: any) {
+        return String(e.error?.errorCode?.code ?? e);
+      }
+      return "";
+    };
+
+    step("Season 1 starts (epoch 10) before the pack is funded");
+    const epoch10 = await chainNow();
+    await record(10, 100, epoch10);
+    await after(epoch10);
+    await program.methods.batchTransferToPacks([{ kol: slot.ticker, amount: slot.target }])
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts([
+        { pubkey: packPda, isWritable: true, isSigner: false },
+        { pubkey: kolRecordFor(slot.ticker), isWritable: true, isSigner: false },
+        { pubkey: slot.mint, isWritable: false, isSigner: false },
+        { pubkey: vaultFor(slot.ticker), isWritable: true, isSigner: false },
+        { pubkey: getAssociatedTokenAddressSync(slot.mint, packPda, true, TOKEN_PROGRAM_ID), isWritable: true, isSigner: false },
+      ])
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const { fundedAt } = await program.account.pack.fetch(packPda);
+    const epoch11 = await after(Math.max(epoch10, fundedAt.toNumber()));
+    await record(11, 400, epoch11);
+    await initLeaderboard(1, 10, 11);
+    const late = await rejection(() => scorePack(1, 10, 11));
+    kv("Pack funded mid-season rejected with", late);
+    if (late !== "PackFundedAfterSeasonStart") throw new Error("packs funded after the season start must not be scored");
+
+    step("Season 2 (epochs 12..=13) starts after the funding");
+    const epoch12 = await after(epoch11);
+    await record(12, 1_000, epoch12);
+    await record(13, 1_600, await after(epoch12));
+    await initLeaderboard(2, 12, 13);
+    const midSeason = await rejection(() => scorePack(2, 12, 12));
+    kv("Mid-season reading rejected with", midSeason);
+    if (midSeason !== "PnlOutsideSeason") throw new Error("score_pack must use the season end reading");
+
+    step("Send score_pack against the start and end readings");
+    await scorePack(2, 12, 13);
+    const kolRecord = await program.account.kolRecord.fetch(kolRecordFor(slot.ticker));
+    const tokens = slot.target.div(new anchor.BN(10).pow(new anchor.BN(kolRecord.decimals)));
+    const expected = tokens.muln(1_600 - 1_000);
+    const pack = await program.account.pack.fetch(packPda);
+    const board = await program.account.leaderboard.fetch(leaderboardFor(2));
+    kv("Score (actual)", pack.score?.value.toString() ?? "none");
+    kv("Score (expected)", expected.toString());
+    if (!pack.score || pack.score.season !== 2) throw new Error("score not stored on the pack");
+    if (!pack.score.value.eq(expected)) throw new Error("score should be the PnL change times whole tokens");
+    if (!board.entries[0]?.pack.equals(packPda) || !board.entries[0].score.eq(expected)) throw new Error("pack should top the leaderboard");
+  });
+
+  it("[TEST21] Tops up a KOL vault and hands back the reservation of a cancelled unfunded pack", async () => {
+    banner("TEST21: VAULT TOP-UP + CANCEL PACK");
+    const [globalPackPoolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_pack_pool")],
+      program.programId
+    );
+    const pda = (seeds: Buffer[]) => anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const kolRecordFor = (kol: string) => pda([Buffer.from("kol"), globalPackPoolAccount.toBuffer(), Buffer.from(kol)]);
+    const vaultFor = (kol: string) => pda([Buffer.from("token_vault"), Buffer.from(kol), globalPackPoolAccount.toBuffer()]);
+
+    step("Send deposit_to_kol_vault");
+    const kol = "SUPER";
+    const mint = mintByTicker[kol];
+    const amount = new anchor.BN(5_000_000); // 5 * 10^6
+    const depositedBefore = (await program.account.kolRecord.fetch(kolRecordFor(kol))).deposited;
+    const vaultBefore = await getAccount(connection, vaultFor(kol), "confirmed");
+    await program.methods.depositToKolVault(kol, amount)
+      .accountsPartial({
+        globalPackPool: globalPackPoolAccount,
+        kolMint: mint,
+        kolTokenVault: vaultFor(kol),
+        depositor: admin.publicKey,
+        depositorTokenAccount: getAssociatedTokenAddressSync(mint, admin.publicKey, false, TOKEN_PROGRAM_ID),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const deposited = (await program.account.kolRecord.fetch(kolRecordFor(kol))).deposited;
+    const vaultAfter = await getAccount(connection, vaultFor(kol), "confirmed");
+    kv("Deposited", `${depositedBefore} -> ${deposited}`);
+    if (!deposited.sub(depositedBefore).eq(amount)) throw new Error("deposit was not counted");
+    if ((vaultAfter.amount - vaultBefore.amount).toString() !== amount.toString()) throw new Error("vault did not receive the deposit");
+
+    step("Commit and reveal a pack that is never funded");
+    const pool = await program.account.globalPackPool.fetch(globalPackPoolAccount);
+    const packPda = pda([Buffer.from("pack"), pool.packCount.toArrayLike(Buffer, "le", 8)]);
+    const packSize = 2;
+    await retryRpc(() =>
+      program.methods
+        .commitPack(admin.publicKey, packSize)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, packAccount: packPda })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+    );
+    const committed = await program.account.pack.fetch(packPda);
+    const pending = (await program.account.globalPackPool.fetch(globalPackPoolAccount)).unrevealedPacks;
+    kv("Unrevealed packs", pending);
+    if (pending !== pool.unrevealedPacks + 1) throw new Error("commit should count as an unrevealed pack");
+
+    step("Tiers are frozen while a pack waits for its reveal");
+    let frozen = "";
+    try {
+      await program.methods.setPackTiers(pool.tiers)
+        .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+    } catch (e: any) {
+      frozen = String(e.error?.errorCode?.code ?? e);
+    }
+    kv("Rejected with", frozen);
+    if (frozen !== "RevealsPending") throw new Error("tiers must not change under an unrevealed pack");
+
+    while ((await connection.getSlot("confirmed")) <= committed.revealSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    const drawnKols = await previewDraw(packPda, committed.revealSlot.toNumber(), committed.id, packSize);
+    const allocatedOf = async () =>
+      Promise.all(drawnKols.map(async (k) => (await program.account.kolRecord.fetch(kolRecordFor(k))).allocated));
+    const before = await allocatedOf();
+    await retryRpc(() =>
+      program.methods
+        .packReveal()
+        .accountsPartial({
+          globalPackPool: globalPackPoolAccount,
+          packAccount: packPda,
+          slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+        })
+        .remainingAccounts(drawnKols.map((k) => ({ pubkey: kolRecordFor(k), isWritable: true, isSigner: false })))
+        .rpc({ commitment: "confirmed" })
+    );
+    const revealed = await program.account.pack.fetch(packPda);
+    const afterReveal = (await program.account.globalPackPool.fetch(globalPackPoolAccount)).unrevealedPacks;
+    if (afterReveal !== pool.unrevealedPacks) throw new Error("reveal should clear the unrevealed count");
+    revealed.slots.forEach((slot: any) => kv(`${slot.ticker} reserved`, slot.target.toString()));
+
+    step("Send cancel_pack");
+    await program.methods.cancelPack()
+      .accountsPartial({ globalPackPool: globalPackPoolAccount, admin: admin.publicKey, role: null, pack: packPda, payer: admin.publicKey })
+      .remainingAccounts(drawnKols.map((k) => ({ pubkey: kolRecordFor(k), isWritable: true, isSigner: false })))
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const after = await allocatedOf();
+    drawnKols.forEach((k, i) => {
+      kv(`${k} allocated`, `${before[i]} -> ${after[i]}`);
+      if (!after[i].eq(before[i])) throw new Error(`${k} reservation was not released`);
+    });
+    if (await connection.getAccountInfo(packPda, "confirmed")) throw new Error("cancelled pack should be closed");
+  });
+});